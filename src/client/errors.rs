@@ -0,0 +1,110 @@
+use reqwest::{Response, StatusCode};
+use thiserror::Error;
+
+pub use crate::driving::rest_handler::errors::Problem;
+
+/// Client side counterpart of the server's `ApiError`
+///
+/// Carries the problem details served along with the error status.
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("Request cannot be handled: {}", .0.detail)]
+    BadRequest(Problem),
+    #[error("Item not found: {}", .0.detail)]
+    NotFound(Problem),
+    #[error("Conflicting Item: {}", .0.detail)]
+    Conflict(Problem),
+    #[error("Unauthorized: {}", .0.detail)]
+    Unauthorized(Problem),
+    #[error("Forbidden: {}", .0.detail)]
+    Forbidden(Problem),
+    #[error("Validation Error: {:?}", .0.errors)]
+    ValidationError(Problem),
+    #[error("Unknown: {}", .0.detail)]
+    Unknown(Problem),
+    #[error("Transport Error: {0}")]
+    Transport(#[from] reqwest::Error),
+}
+
+impl ClientError {
+    /// HTTP status the error was derived from, if the server answered at all
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            ClientError::Transport(e) => e.status(),
+            _ => self
+                .problem()
+                .and_then(|p| StatusCode::from_u16(p.status).ok()),
+        }
+    }
+
+    /// Problem details served by the server, if the server answered at all
+    pub fn problem(&self) -> Option<&Problem> {
+        match self {
+            ClientError::BadRequest(p)
+            | ClientError::NotFound(p)
+            | ClientError::Conflict(p)
+            | ClientError::Unauthorized(p)
+            | ClientError::Forbidden(p)
+            | ClientError::ValidationError(p)
+            | ClientError::Unknown(p) => Some(p),
+            ClientError::Transport(_) => None,
+        }
+    }
+
+    /// Machine-readable error code, e.g. `duplicate`
+    pub fn code(&self) -> Option<&str> {
+        self.problem().map(|p| p.code.as_str())
+    }
+
+    /// Maps an unsuccessful response back to the `ApiError` it was created from
+    pub(crate) async fn from_response(response: Response) -> Self {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        let problem = problem(status, &body);
+
+        match status {
+            StatusCode::BAD_REQUEST => ClientError::BadRequest(problem),
+            StatusCode::NOT_FOUND => ClientError::NotFound(problem),
+            StatusCode::CONFLICT => ClientError::Conflict(problem),
+            StatusCode::UNAUTHORIZED => ClientError::Unauthorized(problem),
+            StatusCode::FORBIDDEN => ClientError::Forbidden(problem),
+            StatusCode::UNPROCESSABLE_ENTITY => ClientError::ValidationError(problem),
+            _ => ClientError::Unknown(problem),
+        }
+    }
+}
+
+/// Error bodies are problem details, fall back to the raw body otherwise
+fn problem(status: StatusCode, body: &str) -> Problem {
+    serde_json::from_str(body).unwrap_or_else(|_| Problem::new(status.as_u16(), "unknown", body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn problem_json_body_parsed() {
+        let expected = Problem::new(409, "duplicate", "Duplicate");
+        let body = serde_json::to_string(&expected).unwrap();
+
+        assert_eq!(problem(StatusCode::CONFLICT, &body), expected);
+    }
+
+    #[test]
+    fn problem_plain_body_kept_as_detail() {
+        let problem = problem(StatusCode::BAD_GATEWAY, "oops");
+
+        assert_eq!(problem.code, "unknown");
+        assert_eq!(problem.detail, "oops");
+        assert_eq!(problem.status, 502);
+    }
+
+    #[test]
+    fn status_and_code_from_problem() {
+        let error = ClientError::Conflict(Problem::new(409, "duplicate", "Duplicate"));
+
+        assert_eq!(error.status(), Some(StatusCode::CONFLICT));
+        assert_eq!(error.code(), Some("duplicate"));
+    }
+}
@@ -0,0 +1,334 @@
+pub mod errors;
+
+use reqwest::{Client, RequestBuilder};
+use serde::de::DeserializeOwned;
+
+pub use crate::client::errors::ClientError;
+pub use crate::domain::conjugation::{Form, Person, Tense, TenseForms};
+pub use crate::domain::quiz::Progress;
+pub use crate::driving::rest_handler::accounts::{CredentialsRequest, TokenResponse, UserResponse};
+pub use crate::driving::rest_handler::decks::{CreateDeckRequest, DeckResponse, EditorRequest};
+pub use crate::driving::rest_handler::health::{HealthCheck, HealthResponse};
+pub use crate::driving::rest_handler::quiz::{
+    ConjugationAnswerRequest, ConjugationAnswerResponse, ConjugationQuestionResponse,
+    QuizAnswerRequest, QuizAnswerResponse, QuizQuestion,
+};
+pub use crate::driving::rest_handler::vocis::{
+    ConjugationRequest, CreateTranslationRequest, LinkRequest, LinkResponse, LinkedResponse,
+    LinksParam, RequestTranslationByWord, RevertRequest, RevisionResponse, SearchRequest,
+    TranslationResponse,
+};
+
+const TRANSLATIONS_ROUTE: &str = "voci/api/v1/translations";
+const QUIZ_ROUTE: &str = "voci/api/v1/quiz";
+const HEALTH_ROUTE: &str = "voci/health";
+const USERS_ROUTE: &str = "voci/api/v1/users";
+const LOGIN_ROUTE: &str = "voci/api/v1/login";
+const DECKS_ROUTE: &str = "voci/api/v1/decks";
+const API_KEY_HEADER: &str = "x-api-key";
+
+/// Typed async client for the REST API
+///
+/// Shares its request and response types with the server, so the wire format is only
+/// defined once.
+#[derive(Clone, Debug)]
+pub struct VociClient {
+    client: Client,
+    base_url: String,
+    api_key: Option<String>,
+    token: Option<String>,
+    deck: Option<String>,
+}
+
+impl VociClient {
+    /// `base_url` is the address of the server, e.g. `http://localhost:8082`
+    pub fn new(base_url: &str) -> Self {
+        Self::with_client(Client::new(), base_url)
+    }
+
+    /// Same as `new` but reuses an already configured `reqwest::Client`
+    pub fn with_client(client: Client, base_url: &str) -> Self {
+        VociClient {
+            client,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: None,
+            token: None,
+            deck: None,
+        }
+    }
+
+    /// Sends `key` in the `X-Api-Key` header of every request
+    pub fn with_api_key(mut self, key: &str) -> Self {
+        self.api_key = Some(key.to_string());
+        self
+    }
+
+    /// Sends `token` as bearer token, requests then work on the vocabulary of its user
+    pub fn with_token(mut self, token: &str) -> Self {
+        self.token = Some(token.to_string());
+        self
+    }
+
+    /// Works on the deck with id `deck` instead of the own vocabulary
+    pub fn with_deck(mut self, deck: &str) -> Self {
+        self.deck = Some(deck.to_string());
+        self
+    }
+
+    pub async fn register(
+        &self,
+        request: &CredentialsRequest,
+    ) -> Result<UserResponse, ClientError> {
+        let builder = self.client.post(self.url(USERS_ROUTE)).json(request);
+        self.send_json(builder).await
+    }
+
+    /// Logs in, pass the returned `access_token` to `with_token`
+    pub async fn login(&self, request: &CredentialsRequest) -> Result<TokenResponse, ClientError> {
+        let builder = self.client.post(self.url(LOGIN_ROUTE)).json(request);
+        self.send_json(builder).await
+    }
+
+    pub async fn create_deck(
+        &self,
+        request: &CreateDeckRequest,
+    ) -> Result<DeckResponse, ClientError> {
+        let builder = self.client.post(self.url(DECKS_ROUTE)).json(request);
+        self.send_json(builder).await
+    }
+
+    pub async fn add_editor(
+        &self,
+        deck: &str,
+        request: &EditorRequest,
+    ) -> Result<DeckResponse, ClientError> {
+        let builder = self
+            .client
+            .post(self.url(&format!("{DECKS_ROUTE}/{deck}/editors")))
+            .json(request);
+        self.send_json(builder).await
+    }
+
+    pub async fn subscribe(&self, deck: &str) -> Result<DeckResponse, ClientError> {
+        let builder = self
+            .client
+            .put(self.url(&format!("{DECKS_ROUTE}/{deck}/subscription")));
+        self.send_json(builder).await
+    }
+
+    pub async fn unsubscribe(&self, deck: &str) -> Result<DeckResponse, ClientError> {
+        let builder = self
+            .client
+            .delete(self.url(&format!("{DECKS_ROUTE}/{deck}/subscription")));
+        self.send_json(builder).await
+    }
+
+    pub async fn create_translation(
+        &self,
+        request: &CreateTranslationRequest,
+    ) -> Result<TranslationResponse, ClientError> {
+        let builder = self.client.post(self.url(TRANSLATIONS_ROUTE)).json(request);
+        self.send_json(builder).await
+    }
+
+    /// Reads the records of a word, one per sense
+    pub async fn read_translation(
+        &self,
+        request: &RequestTranslationByWord,
+    ) -> Result<Vec<TranslationResponse>, ClientError> {
+        let builder = self.client.get(self.url(TRANSLATIONS_ROUTE)).json(request);
+        self.send_json(builder).await
+    }
+
+    /// Same as `read_translation` with the linked records of each sense along
+    pub async fn read_translation_with_links(
+        &self,
+        request: &RequestTranslationByWord,
+    ) -> Result<Vec<TranslationResponse>, ClientError> {
+        let builder = self
+            .client
+            .get(self.url(TRANSLATIONS_ROUTE))
+            .query(&LinksParam { links: true })
+            .json(request);
+        self.send_json(builder).await
+    }
+
+    pub async fn link_translations(
+        &self,
+        id: &str,
+        request: &LinkRequest,
+    ) -> Result<LinkResponse, ClientError> {
+        let builder = self
+            .client
+            .post(self.url(&format!("{TRANSLATIONS_ROUTE}/{id}/links")))
+            .json(request);
+        self.send_json(builder).await
+    }
+
+    pub async fn conjugate_translation(
+        &self,
+        request: &ConjugationRequest,
+    ) -> Result<TranslationResponse, ClientError> {
+        let builder = self
+            .client
+            .put(self.url(&format!("{TRANSLATIONS_ROUTE}/conjugation")))
+            .json(request);
+        self.send_json(builder).await
+    }
+
+    pub async fn update_translation(
+        &self,
+        request: &CreateTranslationRequest,
+    ) -> Result<TranslationResponse, ClientError> {
+        let builder = self.client.put(self.url(TRANSLATIONS_ROUTE)).json(request);
+        self.send_json(builder).await
+    }
+
+    pub async fn delete_translation(
+        &self,
+        request: &RequestTranslationByWord,
+    ) -> Result<(), ClientError> {
+        let builder = self
+            .client
+            .delete(self.url(TRANSLATIONS_ROUTE))
+            .json(request);
+        self.send(builder).await?;
+        Ok(())
+    }
+
+    pub async fn trash(&self) -> Result<Vec<TranslationResponse>, ClientError> {
+        let builder = self
+            .client
+            .get(self.url(&format!("{TRANSLATIONS_ROUTE}/trash")));
+        self.send_json(builder).await
+    }
+
+    pub async fn restore_translation(&self, id: &str) -> Result<TranslationResponse, ClientError> {
+        let builder = self
+            .client
+            .post(self.url(&format!("{TRANSLATIONS_ROUTE}/{id}/restore")));
+        self.send_json(builder).await
+    }
+
+    pub async fn history(&self, id: &str) -> Result<Vec<RevisionResponse>, ClientError> {
+        let builder = self
+            .client
+            .get(self.url(&format!("{TRANSLATIONS_ROUTE}/{id}/history")));
+        self.send_json(builder).await
+    }
+
+    pub async fn revert_translation(
+        &self,
+        id: &str,
+        request: &RevertRequest,
+    ) -> Result<TranslationResponse, ClientError> {
+        let builder = self
+            .client
+            .post(self.url(&format!("{TRANSLATIONS_ROUTE}/{id}/revert")))
+            .json(request);
+        self.send_json(builder).await
+    }
+
+    pub async fn search_translations(
+        &self,
+        request: &SearchRequest,
+    ) -> Result<Vec<TranslationResponse>, ClientError> {
+        let builder = self
+            .client
+            .get(self.url(&format!("{TRANSLATIONS_ROUTE}/search")))
+            .query(request);
+        self.send_json(builder).await
+    }
+
+    pub async fn draw_quiz(&self, size: usize) -> Result<Vec<QuizQuestion>, ClientError> {
+        let builder = self
+            .client
+            .get(self.url(QUIZ_ROUTE))
+            .query(&[("size", size)]);
+        self.send_json(builder).await
+    }
+
+    pub async fn answer_quiz(
+        &self,
+        request: &QuizAnswerRequest,
+    ) -> Result<QuizAnswerResponse, ClientError> {
+        let builder = self
+            .client
+            .post(self.url(&format!("{QUIZ_ROUTE}/answer")))
+            .json(request);
+        self.send_json(builder).await
+    }
+
+    pub async fn draw_conjugation(&self) -> Result<ConjugationQuestionResponse, ClientError> {
+        let builder = self
+            .client
+            .get(self.url(&format!("{QUIZ_ROUTE}/conjugation")));
+        self.send_json(builder).await
+    }
+
+    pub async fn answer_conjugation(
+        &self,
+        request: &ConjugationAnswerRequest,
+    ) -> Result<ConjugationAnswerResponse, ClientError> {
+        let builder = self
+            .client
+            .post(self.url(&format!("{QUIZ_ROUTE}/conjugation")))
+            .json(request);
+        self.send_json(builder).await
+    }
+
+    /// Whether the server and its dependencies are ready to serve requests
+    pub async fn ready(&self) -> Result<HealthResponse, ClientError> {
+        let builder = self.client.get(self.url(&format!("{HEALTH_ROUTE}/ready")));
+        self.send_json(builder).await
+    }
+
+    fn url(&self, route: &str) -> String {
+        format!("{}/{route}", self.base_url)
+    }
+
+    async fn send(&self, builder: RequestBuilder) -> Result<reqwest::Response, ClientError> {
+        let builder = match &self.api_key {
+            Some(key) => builder.header(API_KEY_HEADER, key),
+            None => builder,
+        };
+        let builder = match &self.token {
+            Some(token) => builder.bearer_auth(token),
+            None => builder,
+        };
+        let builder = match &self.deck {
+            Some(deck) => builder.query(&[("deck", deck)]),
+            None => builder,
+        };
+        let response = builder.send().await?;
+
+        if response.status().is_success() {
+            Ok(response)
+        } else {
+            Err(ClientError::from_response(response).await)
+        }
+    }
+
+    async fn send_json<T: DeserializeOwned>(
+        &self,
+        builder: RequestBuilder,
+    ) -> Result<T, ClientError> {
+        let response = self.send(builder).await?;
+        Ok(response.json().await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn url_trailing_slash_trimmed() {
+        let client = VociClient::new("http://localhost:8082/");
+
+        assert_eq!(
+            client.url(TRANSLATIONS_ROUTE),
+            "http://localhost:8082/voci/api/v1/translations"
+        );
+    }
+}
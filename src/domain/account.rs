@@ -0,0 +1,183 @@
+use argon2::Argon2;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use std::sync::LazyLock;
+use thiserror::Error;
+use tracing::instrument;
+
+use crate::domain::ports::{RepoCreateError, RepoReadError, UserRepository};
+use crate::domain::user::User;
+
+pub const MAX_NAME_LEN: usize = 64;
+pub const MIN_PASSWORD_LEN: usize = 8;
+
+#[derive(Debug, PartialEq, Error)]
+pub enum AccountError {
+    #[error("Name must be 1 to {MAX_NAME_LEN} characters without whitespace")]
+    InvalidName,
+    #[error("Password must be at least {MIN_PASSWORD_LEN} characters")]
+    WeakPassword,
+    #[error("Name is taken")]
+    NameTaken,
+    #[error("Invalid name or password")]
+    InvalidCredentials,
+    #[error("Read Error: {0}")]
+    Read(#[from] RepoReadError),
+    #[error("Create Error")]
+    Create(#[from] RepoCreateError),
+    #[error("Cannot hash password: {0}")]
+    Hash(String),
+}
+
+/// Verified against for unknown names, so that they take as long as wrong passwords
+static DUMMY_HASH: LazyLock<String> = LazyLock::new(|| {
+    let salt = SaltString::encode_b64(&[0; 16]).expect("16 bytes are a valid salt");
+    Argon2::default()
+        .hash_password(b"no such user", &salt)
+        .expect("hashing with default parameters succeeds")
+        .to_string()
+});
+
+/// Creates a user account, the password is stored as argon2 hash
+#[instrument(skip_all, fields(name = %name), err(level = "info"))]
+pub async fn register(
+    repository: &impl UserRepository,
+    name: &str,
+    password: &str,
+) -> Result<User, AccountError> {
+    if name.is_empty()
+        || name.chars().count() > MAX_NAME_LEN
+        || name.chars().any(char::is_whitespace)
+    {
+        return Err(AccountError::InvalidName);
+    }
+    if password.chars().count() < MIN_PASSWORD_LEN {
+        return Err(AccountError::WeakPassword);
+    }
+
+    match repository.read_user_by_name(name).await {
+        Ok(_) => return Err(AccountError::NameTaken),
+        Err(RepoReadError::NotFound) => {}
+        Err(e) => return Err(e.into()),
+    }
+
+    let salt = SaltString::encode_b64(&rand::random::<[u8; 16]>())
+        .map_err(|e| AccountError::Hash(e.to_string()))?;
+    let password_hash = Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map_err(|e| AccountError::Hash(e.to_string()))?
+        .to_string();
+
+    // The read above is only a shortcut, a concurrent registration is caught on insert
+    match repository.create_user(name, &password_hash).await {
+        Ok(user) => Ok(user),
+        Err(RepoCreateError::Duplicate) => Err(AccountError::NameTaken),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Checks name and password, unknown names and wrong passwords are not told apart
+///
+/// Not even by the time taken, a password is verified against a dummy hash for unknown names.
+#[instrument(skip_all, fields(name = %name), err(level = "info"))]
+pub async fn login(
+    repository: &impl UserRepository,
+    name: &str,
+    password: &str,
+) -> Result<User, AccountError> {
+    let user = match repository.read_user_by_name(name).await {
+        Ok(user) => Some(user),
+        Err(RepoReadError::NotFound) => None,
+        Err(e) => return Err(e.into()),
+    };
+
+    let stored = user
+        .as_ref()
+        .map_or(DUMMY_HASH.as_str(), User::password_hash);
+    let hash = PasswordHash::new(stored).map_err(|e| AccountError::Hash(e.to_string()))?;
+    let verified = Argon2::default()
+        .verify_password(password.as_bytes(), &hash)
+        .is_ok();
+
+    match user {
+        Some(user) if verified => Ok(user),
+        _ => Err(AccountError::InvalidCredentials),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::domain::ports::TranslationRepository;
+    use crate::test_utils::{utils::shared::*, voci_repo_double::repo_double::VociRepoDouble};
+
+    const PASSWORD: &str = "c0rrect horse";
+
+    #[actix_rt::test]
+    async fn register_then_login_ok() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+
+        let registered = register(&repo, OWNER, PASSWORD).await.unwrap();
+        let logged_in = login(&repo, OWNER, PASSWORD).await.unwrap();
+
+        assert_eq!(logged_in.id(), registered.id());
+        assert_ne!(registered.password_hash(), PASSWORD);
+        assert!(registered.password_hash().starts_with("$argon2id$"));
+    }
+
+    #[actix_rt::test]
+    async fn register_taken_name_error() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        repo.create_user(OWNER, "hash").await.unwrap();
+
+        let result = register(&repo, OWNER, PASSWORD).await;
+
+        assert_eq!(result, Err(AccountError::NameTaken));
+    }
+
+    #[actix_rt::test]
+    async fn register_same_name_twice_error() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+
+        register(&repo, OWNER, PASSWORD).await.unwrap();
+        let result = register(&repo, OWNER, PASSWORD).await;
+
+        assert_eq!(result, Err(AccountError::NameTaken));
+        assert!(login(&repo, OWNER, PASSWORD).await.is_ok());
+    }
+
+    #[actix_rt::test]
+    async fn register_bad_input_error() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+
+        assert_eq!(
+            register(&repo, "al ice", PASSWORD).await,
+            Err(AccountError::InvalidName)
+        );
+        assert_eq!(
+            register(&repo, OWNER, "short").await,
+            Err(AccountError::WeakPassword)
+        );
+    }
+
+    #[test]
+    fn dummy_hash_is_argon2() {
+        assert!(PasswordHash::new(&DUMMY_HASH).is_ok());
+        assert!(DUMMY_HASH.starts_with("$argon2id$"));
+    }
+
+    #[actix_rt::test]
+    async fn login_wrong_password_or_unknown_name_error() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        register(&repo, OWNER, PASSWORD).await.unwrap();
+
+        assert_eq!(
+            login(&repo, OWNER, "wrong password").await,
+            Err(AccountError::InvalidCredentials)
+        );
+        assert_eq!(
+            login(&repo, OTHER_OWNER, PASSWORD).await,
+            Err(AccountError::InvalidCredentials)
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use thiserror::Error;
+
+/// What a key is allowed to do, each scope includes the ones before it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scope {
+    Read,
+    Write,
+    Admin,
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Scope::Read => write!(f, "read"),
+            Scope::Write => write!(f, "write"),
+            Scope::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+/// An API key as stored, only the SHA-256 hash of the key itself is kept
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiKey {
+    pub name: String,
+    /// Lowercase hex encoded SHA-256 hash of the key
+    pub hash: String,
+    pub scopes: Vec<Scope>,
+}
+
+impl ApiKey {
+    pub fn grants(&self, required: Scope) -> bool {
+        self.scopes.iter().any(|scope| *scope >= required)
+    }
+}
+
+#[derive(Debug, PartialEq, Error)]
+pub enum AuthError {
+    #[error("API key required")]
+    MissingKey,
+    #[error("API key unknown")]
+    UnknownKey,
+    #[error("API key {0} lacks scope {1}")]
+    MissingScope(String, Scope),
+}
+
+pub fn hash_api_key(key: &str) -> String {
+    format!("{:x}", Sha256::digest(key.as_bytes()))
+}
+
+/// Finds the key among `keys` and checks that it grants `required`
+pub fn authorize<'a>(
+    keys: &'a [ApiKey],
+    key: Option<&str>,
+    required: Scope,
+) -> Result<&'a ApiKey, AuthError> {
+    let key = key.ok_or(AuthError::MissingKey)?;
+    let hash = hash_api_key(key);
+
+    let api_key = keys
+        .iter()
+        .find(|k| k.hash.eq_ignore_ascii_case(&hash))
+        .ok_or(AuthError::UnknownKey)?;
+
+    if api_key.grants(required) {
+        Ok(api_key)
+    } else {
+        Err(AuthError::MissingScope(api_key.name.clone(), required))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn keys() -> Vec<ApiKey> {
+        vec![
+            ApiKey {
+                name: "reader".to_string(),
+                hash: hash_api_key("r3ad"),
+                scopes: vec![Scope::Read],
+            },
+            ApiKey {
+                name: "admin".to_string(),
+                hash: hash_api_key("adm1n"),
+                scopes: vec![Scope::Admin],
+            },
+        ]
+    }
+
+    #[test]
+    fn hash_is_hex_sha256() {
+        assert_eq!(
+            hash_api_key("secret"),
+            "2bb80d537b1da3e38bd30361aa855686bde0eacd7162fef6a25fe97bf527a25b"
+        );
+    }
+
+    #[test]
+    fn authorize_key_with_scope_ok() {
+        let keys = keys();
+
+        assert_eq!(
+            authorize(&keys, Some("r3ad"), Scope::Read).map(|k| k.name.as_str()),
+            Ok("reader")
+        );
+        assert_eq!(
+            authorize(&keys, Some("adm1n"), Scope::Write).map(|k| k.name.as_str()),
+            Ok("admin")
+        );
+    }
+
+    #[test]
+    fn authorize_missing_or_unknown_key_error() {
+        let keys = keys();
+
+        assert_eq!(
+            authorize(&keys, None, Scope::Read),
+            Err(AuthError::MissingKey)
+        );
+        assert_eq!(
+            authorize(&keys, Some("guess"), Scope::Read),
+            Err(AuthError::UnknownKey)
+        );
+    }
+
+    #[test]
+    fn authorize_key_lacking_scope_error() {
+        let keys = keys();
+
+        assert_eq!(
+            authorize(&keys, Some("r3ad"), Scope::Write),
+            Err(AuthError::MissingScope("reader".to_string(), Scope::Write))
+        );
+    }
+}
@@ -0,0 +1,433 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use thiserror::Error;
+use tracing::instrument;
+use utoipa::ToSchema;
+
+use crate::domain::deck::Access;
+use crate::domain::ports::{
+    RepoReadError, RepoUpdateError, RevisionRepository, TranslationRepository,
+};
+use crate::domain::revision::record_revision;
+use crate::domain::voci::{
+    Lang, TranslationRecord, TranslationRecordError, Word, checked, select_sense,
+};
+
+/// Tenses conjugation tables are kept for
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Tense {
+    Present,
+    Imparfait,
+    Futur,
+    Conditionnel,
+}
+
+impl fmt::Display for Tense {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Tense::Present => write!(f, "présent"),
+            Tense::Imparfait => write!(f, "imparfait"),
+            Tense::Futur => write!(f, "futur"),
+            Tense::Conditionnel => write!(f, "conditionnel"),
+        }
+    }
+}
+
+/// Grammatical person, the forms of a tense are ordered as in `Person::ALL`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Person {
+    Je,
+    Tu,
+    Il,
+    Nous,
+    Vous,
+    Ils,
+}
+
+impl Person {
+    pub const ALL: [Person; 6] = [
+        Person::Je,
+        Person::Tu,
+        Person::Il,
+        Person::Nous,
+        Person::Vous,
+        Person::Ils,
+    ];
+
+    fn index(&self) -> usize {
+        Person::ALL.iter().position(|p| p == self).unwrap()
+    }
+}
+
+impl fmt::Display for Person {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Person::Je => write!(f, "je"),
+            Person::Tu => write!(f, "tu"),
+            Person::Il => write!(f, "il"),
+            Person::Nous => write!(f, "nous"),
+            Person::Vous => write!(f, "vous"),
+            Person::Ils => write!(f, "ils"),
+        }
+    }
+}
+
+/// The forms of one tense, one per person in the order of `Person::ALL`, without pronouns
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TenseForms {
+    pub tense: Tense,
+    pub forms: Vec<String>,
+}
+
+/// A single form, e.g. the irregular one replacing a generated form
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Form {
+    pub tense: Tense,
+    pub person: Person,
+    pub form: String,
+}
+
+#[derive(Debug, PartialEq, Error)]
+pub enum ConjugationError {
+    #[error("Not a verb of a regular pattern, -er, -ir or -re: {0}")]
+    NotRegular(String),
+    #[error("No tenses given")]
+    NoTenses,
+    #[error("Tense {0} needs a form for each of the six persons")]
+    FormCount(Tense),
+    #[error("Tense {0} given twice")]
+    DuplicateTense(Tense),
+    #[error("Tense {0} missing in the table")]
+    MissingTense(Tense),
+    #[error("Bad Input: {0}")]
+    Word(#[from] TranslationRecordError),
+    #[error("Read Error: {0}")]
+    Read(#[from] RepoReadError),
+    #[error("Update Error")]
+    Update(#[from] RepoUpdateError),
+    #[error("Read-only access")]
+    ReadOnly,
+}
+
+/// Conjugation table of a French verb
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Conjugation {
+    tenses: Vec<TenseForms>,
+}
+
+/// Endings of the present tense by verb group, `-ir` after the pattern of "finir"
+const PRESENT_ER: [&str; 6] = ["e", "es", "e", "ons", "ez", "ent"];
+const PRESENT_IR: [&str; 6] = ["is", "is", "it", "issons", "issez", "issent"];
+const PRESENT_RE: [&str; 6] = ["s", "s", "", "ons", "ez", "ent"];
+const IMPARFAIT: [&str; 6] = ["ais", "ais", "ait", "ions", "iez", "aient"];
+const FUTUR: [&str; 6] = ["ai", "as", "a", "ons", "ez", "ont"];
+
+impl Conjugation {
+    /// A manually entered table, each tense given at most once with a form for every person
+    pub fn new(tenses: Vec<TenseForms>) -> Result<Self, ConjugationError> {
+        if tenses.is_empty() {
+            return Err(ConjugationError::NoTenses);
+        }
+
+        let mut checked_tenses: Vec<TenseForms> = Vec::new();
+        for TenseForms { tense, forms } in tenses {
+            if checked_tenses.iter().any(|t| t.tense == tense) {
+                return Err(ConjugationError::DuplicateTense(tense));
+            }
+            if forms.len() != Person::ALL.len() {
+                return Err(ConjugationError::FormCount(tense));
+            }
+            let forms = forms
+                .iter()
+                .map(|f| checked(f, TranslationRecordError::EmptyWord))
+                .collect::<Result<Vec<_>, _>>()?;
+            checked_tenses.push(TenseForms { tense, forms });
+        }
+
+        Ok(Conjugation {
+            tenses: checked_tenses,
+        })
+    }
+
+    /// The table of all tenses after the regular pattern of the ending of `infinitive`
+    ///
+    /// Spelling changes such as "mangeons" or "commençons" are not made, they are overrides
+    /// as are the forms of irregular verbs.
+    pub fn regular(infinitive: &str) -> Result<Self, ConjugationError> {
+        let infinitive = checked(infinitive, TranslationRecordError::EmptyWord)?;
+        let not_regular = || ConjugationError::NotRegular(infinitive.clone());
+        let split = infinitive
+            .char_indices()
+            .rev()
+            .nth(1)
+            .map(|(i, _)| i)
+            .ok_or_else(not_regular)?;
+        let (stem, ending) = infinitive.split_at(split);
+        if stem.is_empty() {
+            return Err(not_regular());
+        }
+
+        let present = match ending {
+            "er" => PRESENT_ER,
+            "ir" => PRESENT_IR,
+            "re" => PRESENT_RE,
+            _ => return Err(not_regular()),
+        };
+        let future_stem = infinitive.strip_suffix('e').unwrap_or(&infinitive);
+        let nous = format!("{stem}{}", present[Person::Nous.index()]);
+        let imparfait_stem = nous.strip_suffix("ons").unwrap_or(&nous);
+
+        let forms =
+            |stem: &str, endings: [&str; 6]| endings.iter().map(|e| format!("{stem}{e}")).collect();
+        Conjugation::new(vec![
+            TenseForms {
+                tense: Tense::Present,
+                forms: forms(stem, present),
+            },
+            TenseForms {
+                tense: Tense::Imparfait,
+                forms: forms(imparfait_stem, IMPARFAIT),
+            },
+            TenseForms {
+                tense: Tense::Futur,
+                forms: forms(future_stem, FUTUR),
+            },
+            TenseForms {
+                tense: Tense::Conditionnel,
+                forms: forms(future_stem, IMPARFAIT),
+            },
+        ])
+    }
+
+    /// Replaces single forms, the tense of each must be in the table
+    pub fn with_overrides(mut self, overrides: &[Form]) -> Result<Self, ConjugationError> {
+        for Form {
+            tense,
+            person,
+            form,
+        } in overrides
+        {
+            let forms = self
+                .tenses
+                .iter_mut()
+                .find(|t| t.tense == *tense)
+                .ok_or(ConjugationError::MissingTense(*tense))?;
+            forms.forms[person.index()] = checked(form, TranslationRecordError::EmptyWord)?;
+        }
+        Ok(self)
+    }
+
+    pub fn tenses(&self) -> &[TenseForms] {
+        &self.tenses
+    }
+
+    /// The form of `person` in `tense`, if the table has the tense
+    pub fn form(&self, tense: Tense, person: Person) -> Option<&str> {
+        self.tenses
+            .iter()
+            .find(|t| t.tense == tense)
+            .and_then(|t| t.forms.get(person.index()))
+            .map(String::as_str)
+    }
+}
+
+/// How to fill the conjugation table of a verb
+#[derive(Debug, Clone, PartialEq)]
+pub enum Table {
+    /// Entered tense by tense
+    Manual(Vec<TenseForms>),
+    /// Generated after the regular pattern of the verb's ending
+    Regular,
+}
+
+/// Attaches a conjugation table to the record of a French verb, replacing any former one
+///
+/// `overrides` replace single forms of the table, e.g. the irregular ones of a generated table.
+///
+/// # Errors
+/// Returns `ConjugationError::ReadOnly` if the record belongs to a deck the user only
+/// subscribed to
+#[instrument(skip_all, fields(access = %access, word = %word), err(level = "info"))]
+pub async fn conjugate_translation(
+    repository: &(impl TranslationRepository + RevisionRepository),
+    access: &Access,
+    word: &str,
+    sense: Option<&str>,
+    table: Table,
+    overrides: &[Form],
+) -> Result<TranslationRecord, ConjugationError> {
+    let word = Word::new(word, &Lang::fr)?;
+
+    let senses = repository.read_by_word(access.readable(), &word).await?;
+    let tr = select_sense(senses, sense)?;
+    if !access.can_write(tr.owner()) {
+        return Err(ConjugationError::ReadOnly);
+    }
+
+    let conjugation = match table {
+        Table::Manual(tenses) => Conjugation::new(tenses)?,
+        Table::Regular => Conjugation::regular(word.value().0)?,
+    }
+    .with_overrides(overrides)?;
+
+    let conjugated = repository
+        .update(&tr.clone().with_conjugation(Some(conjugation)))
+        .await?;
+    record_revision(repository, access, Some(tr), Some(conjugated.clone())).await;
+    Ok(conjugated)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::domain::create_translation::create_translation;
+    use crate::test_utils::{utils::shared::*, voci_repo_double::repo_double::VociRepoDouble};
+
+    fn present(c: &Conjugation) -> Vec<&str> {
+        Person::ALL
+            .iter()
+            .map(|p| c.form(Tense::Present, *p).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn regular_er_ir_re_verbs_generated() {
+        let parler = Conjugation::regular("parler").unwrap();
+        let finir = Conjugation::regular("finir").unwrap();
+        let vendre = Conjugation::regular("vendre").unwrap();
+
+        assert_eq!(
+            present(&parler),
+            ["parle", "parles", "parle", "parlons", "parlez", "parlent"]
+        );
+        assert_eq!(
+            present(&finir),
+            [
+                "finis",
+                "finis",
+                "finit",
+                "finissons",
+                "finissez",
+                "finissent"
+            ]
+        );
+        assert_eq!(
+            present(&vendre),
+            ["vends", "vends", "vend", "vendons", "vendez", "vendent"]
+        );
+        assert_eq!(
+            finir.form(Tense::Imparfait, Person::Nous),
+            Some("finissions")
+        );
+        assert_eq!(vendre.form(Tense::Futur, Person::Ils), Some("vendront"));
+        assert_eq!(
+            parler.form(Tense::Conditionnel, Person::Je),
+            Some("parlerais")
+        );
+    }
+
+    #[test]
+    fn regular_of_other_ending_err() {
+        assert_eq!(
+            Conjugation::regular("chien"),
+            Err(ConjugationError::NotRegular("chien".to_string()))
+        );
+        assert_eq!(
+            Conjugation::regular("er"),
+            Err(ConjugationError::NotRegular("er".to_string()))
+        );
+    }
+
+    #[test]
+    fn irregular_forms_overridden() {
+        let override_ = |person, form: &str| Form {
+            tense: Tense::Present,
+            person,
+            form: form.to_string(),
+        };
+
+        let aller = Conjugation::regular("aller")
+            .unwrap()
+            .with_overrides(&[
+                override_(Person::Je, "vais"),
+                override_(Person::Tu, "vas"),
+                override_(Person::Il, "va"),
+                override_(Person::Ils, "vont"),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            present(&aller),
+            ["vais", "vas", "va", "allons", "allez", "vont"]
+        );
+    }
+
+    #[test]
+    fn manual_table_checked() {
+        let forms = |n: usize| vec!["vais".to_string(); n];
+
+        assert_eq!(Conjugation::new(vec![]), Err(ConjugationError::NoTenses));
+        assert_eq!(
+            Conjugation::new(vec![TenseForms {
+                tense: Tense::Present,
+                forms: forms(5),
+            }]),
+            Err(ConjugationError::FormCount(Tense::Present))
+        );
+        assert_eq!(
+            Conjugation::new(vec![
+                TenseForms {
+                    tense: Tense::Futur,
+                    forms: forms(6),
+                },
+                TenseForms {
+                    tense: Tense::Futur,
+                    forms: forms(6),
+                },
+            ]),
+            Err(ConjugationError::DuplicateTense(Tense::Futur))
+        );
+        assert_eq!(
+            Conjugation::new(vec![TenseForms {
+                tense: Tense::Futur,
+                forms: forms(6),
+            }])
+            .unwrap()
+            .with_overrides(&[Form {
+                tense: Tense::Present,
+                person: Person::Je,
+                form: "vais".to_string(),
+            }]),
+            Err(ConjugationError::MissingTense(Tense::Present))
+        );
+    }
+
+    #[actix_rt::test]
+    async fn conjugate_french_verb_table_attached() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        create_translation(
+            &repo,
+            &access(),
+            "parler",
+            &Lang::fr,
+            None,
+            &["sprechen"],
+            &Lang::de,
+        )
+        .await
+        .unwrap();
+
+        let tr = conjugate_translation(&repo, &access(), "parler", None, Table::Regular, &[])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            tr.conjugation()
+                .and_then(|c| c.form(Tense::Present, Person::Nous)),
+            Some("parlons")
+        );
+    }
+}
@@ -1,8 +1,14 @@
 use std::ops::Deref;
 use thiserror::Error;
+use tracing::instrument;
 
-use crate::domain::ports::{RepoCreateError, RepoReadError, TranslationRepository};
+use crate::domain::deck::Access;
+use crate::domain::ports::{
+    RepoCreateError, RepoReadError, RevisionRepository, TranslationRepository,
+};
+use crate::domain::revision::record_revision;
 use crate::domain::voci::{Lang, TranslationRecord, TranslationRecordError};
+use crate::telemetry;
 
 #[derive(Debug, PartialEq, Error)]
 pub enum CreateError {
@@ -14,28 +20,138 @@ pub enum CreateError {
     Create(#[from] RepoCreateError),
     #[error("Duplicate")]
     Duplicate,
+    #[error("Read-only access")]
+    ReadOnly,
 }
 
+/// Creates a record in the writable vocabulary, duplicates are only checked within it
+///
+/// Homographs get a record per `sense`, a word is a duplicate if it has a record of the
+/// same sense already, or both are unlabeled.
+#[instrument(skip_all, fields(access = %access, word = %word, lang = %word_lang), err(level = "info"))]
 pub async fn create_translation<S>(
-    repository: &impl TranslationRepository,
+    repository: &(impl TranslationRepository + RevisionRepository),
+    access: &Access,
     word: &str,
     word_lang: &Lang,
+    sense: Option<&str>,
     translations: &[S],
     translation_lang: &Lang,
 ) -> Result<TranslationRecord, CreateError>
 where
     S: Deref<Target = str>,
 {
-    let tr = TranslationRecord::new(None, word, word_lang, translations, translation_lang)?;
+    let owner = access.writable().ok_or(CreateError::ReadOnly)?;
+    let tr = TranslationRecord::new(None, owner, word, word_lang, translations, translation_lang)?
+        .with_sense(sense)?;
 
     let word = tr.word();
 
-    let does_exist = repository.read_by_word(word).await.is_ok();
+    let does_exist = repository
+        .read_by_word(std::slice::from_ref(owner), word)
+        .await
+        .is_ok_and(|senses| senses.iter().any(|s| s.is_sense(tr.sense())));
 
     if !does_exist {
         let create_response = repository.create(&tr).await?;
+        telemetry::translation_created();
+        record_revision(repository, access, None, Some(create_response.clone())).await;
         Ok(create_response)
     } else {
+        telemetry::duplicate_rejected();
         Err(CreateError::Duplicate)
     }
 }
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::domain::deck::{create_deck, resolve_access, subscribe};
+    use crate::domain::user::UserId;
+    use crate::test_utils::{utils::shared::*, voci_repo_double::repo_double::VociRepoDouble};
+
+    #[actix_rt::test]
+    async fn create_existing_word_of_same_owner_duplicate() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+
+        let result = create_translation(
+            &repo,
+            &access(),
+            WORD,
+            &WORD_LANG,
+            None,
+            &TRANSLATIONS,
+            &TRANSLATION_LANG,
+        )
+        .await;
+
+        assert_eq!(result, Err(CreateError::Duplicate));
+    }
+
+    #[actix_rt::test]
+    async fn create_existing_word_in_other_sense_created() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        let access = access();
+        let create = |sense| {
+            create_translation(
+                &repo,
+                &access,
+                WORD,
+                &WORD_LANG,
+                sense,
+                &TRANSLATIONS,
+                &TRANSLATION_LANG,
+            )
+        };
+
+        let created = create(Some("animal")).await.unwrap();
+
+        assert_eq!(created.sense(), Some("animal"));
+        assert_eq!(create(Some("Animal")).await, Err(CreateError::Duplicate));
+    }
+
+    #[actix_rt::test]
+    async fn create_existing_word_of_other_owner_created() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        let other = UserId::new(OTHER_OWNER);
+
+        let created = create_translation(
+            &repo,
+            &Access::own(&other),
+            WORD,
+            &WORD_LANG,
+            None,
+            &TRANSLATIONS,
+            &TRANSLATION_LANG,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(created.owner(), &other);
+    }
+
+    #[actix_rt::test]
+    async fn create_in_subscribed_deck_read_only() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        let deck = create_deck(&repo, &owner(), "Animals").await.unwrap();
+        let student = UserId::new(OTHER_OWNER);
+        subscribe(&repo, &student, deck.id()).await.unwrap();
+        let student = resolve_access(&repo, &student, Some(deck.id()))
+            .await
+            .unwrap();
+
+        let result = create_translation(
+            &repo,
+            &student,
+            "chat",
+            &WORD_LANG,
+            None,
+            &TRANSLATIONS,
+            &TRANSLATION_LANG,
+        )
+        .await;
+
+        assert_eq!(result, Err(CreateError::ReadOnly));
+    }
+}
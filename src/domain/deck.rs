@@ -0,0 +1,364 @@
+use std::fmt;
+use thiserror::Error;
+use tracing::instrument;
+
+use crate::domain::ports::{
+    DeckRepository, RepoCreateError, RepoReadError, RepoUpdateError, UserRepository,
+};
+use crate::domain::user::UserId;
+
+pub const MAX_DECK_NAME_LEN: usize = 100;
+/// Owner ids of deck vocabularies, they cannot clash with user ids
+const VOCABULARY_PREFIX: &str = "deck:";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DeckId(String);
+
+impl DeckId {
+    pub fn new(id: &str) -> Self {
+        DeckId(id.to_string())
+    }
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for DeckId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A published vocabulary, edited by its owner and editors and read by its subscribers
+///
+/// The records of a deck are owned by the deck itself, see `vocabulary`, so changes of any
+/// editor are seen by all subscribers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deck {
+    id: DeckId,
+    name: String,
+    owner: UserId,
+    editors: Vec<UserId>,
+    subscribers: Vec<UserId>,
+}
+
+impl Deck {
+    pub fn new(
+        id: &str,
+        name: &str,
+        owner: &UserId,
+        editors: Vec<UserId>,
+        subscribers: Vec<UserId>,
+    ) -> Self {
+        Deck {
+            id: DeckId::new(id),
+            name: name.to_string(),
+            owner: owner.clone(),
+            editors,
+            subscribers,
+        }
+    }
+
+    pub fn id(&self) -> &DeckId {
+        &self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn owner(&self) -> &UserId {
+        &self.owner
+    }
+
+    pub fn editors(&self) -> &[UserId] {
+        &self.editors
+    }
+
+    pub fn subscribers(&self) -> &[UserId] {
+        &self.subscribers
+    }
+
+    pub fn can_edit(&self, user: &UserId) -> bool {
+        self.owner == *user || self.editors.contains(user)
+    }
+
+    pub fn is_subscribed(&self, user: &UserId) -> bool {
+        self.subscribers.contains(user)
+    }
+
+    /// Owner of the TranslationRecords of the deck
+    pub fn vocabulary(&self) -> UserId {
+        UserId::new(&format!("{VOCABULARY_PREFIX}{}", self.id))
+    }
+}
+
+/// Whose TranslationRecords a request reads and where it may write
+#[derive(Debug, Clone, PartialEq)]
+pub struct Access {
+    user: UserId,
+    read: Vec<UserId>,
+    write: Option<UserId>,
+}
+
+impl Access {
+    /// Reading and writing the user's own vocabulary only
+    pub fn own(user: &UserId) -> Self {
+        Access {
+            user: user.clone(),
+            read: vec![user.clone()],
+            write: Some(user.clone()),
+        }
+    }
+
+    /// The acting user, learning progress is kept for them
+    pub fn user(&self) -> &UserId {
+        &self.user
+    }
+
+    /// Owners of the readable records, in order of precedence
+    pub fn readable(&self) -> &[UserId] {
+        &self.read
+    }
+
+    /// Owner of created records, `None` for read-only access
+    pub fn writable(&self) -> Option<&UserId> {
+        self.write.as_ref()
+    }
+
+    pub fn can_write(&self, owner: &UserId) -> bool {
+        self.write.as_ref() == Some(owner)
+    }
+}
+
+impl fmt::Display for Access {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.write {
+            Some(write) if *write != self.user => write!(f, "{} on {write}", self.user),
+            _ => write!(f, "{}", self.user),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Error)]
+pub enum DeckError {
+    #[error("Deck name must be 1 to {MAX_DECK_NAME_LEN} characters")]
+    InvalidName,
+    #[error("Decks need a logged in user")]
+    LoginRequired,
+    #[error("Deck not found")]
+    NotFound,
+    #[error("Only the owner of the deck may do this")]
+    NotOwner,
+    #[error("Not subscribed to the deck")]
+    NotSubscribed,
+    #[error("Unknown user {0}")]
+    UnknownUser(String),
+    #[error("Read Error: {0}")]
+    Read(RepoReadError),
+    #[error("Create Error")]
+    Create(#[from] RepoCreateError),
+    #[error("Update Error: {0}")]
+    Update(RepoUpdateError),
+}
+
+impl From<RepoReadError> for DeckError {
+    fn from(e: RepoReadError) -> Self {
+        match e {
+            RepoReadError::NotFound => DeckError::NotFound,
+            e => DeckError::Read(e),
+        }
+    }
+}
+
+impl From<RepoUpdateError> for DeckError {
+    fn from(e: RepoUpdateError) -> Self {
+        match e {
+            RepoUpdateError::NotFound | RepoUpdateError::BadId => DeckError::NotFound,
+            e => DeckError::Update(e),
+        }
+    }
+}
+
+fn require_login(user: &UserId) -> Result<(), DeckError> {
+    if *user == UserId::anonymous() {
+        Err(DeckError::LoginRequired)
+    } else {
+        Ok(())
+    }
+}
+
+/// Creates an empty deck owned by `user`
+#[instrument(skip_all, fields(user = %user, name = %name), err(level = "info"))]
+pub async fn create_deck(
+    repository: &impl DeckRepository,
+    user: &UserId,
+    name: &str,
+) -> Result<Deck, DeckError> {
+    require_login(user)?;
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_DECK_NAME_LEN {
+        return Err(DeckError::InvalidName);
+    }
+
+    Ok(repository.create_deck(name, user).await?)
+}
+
+/// Lets the user named `editor` edit the deck, only its owner may do so
+#[instrument(skip_all, fields(user = %user, deck = %deck, editor = %editor), err(level = "info"))]
+pub async fn add_editor(
+    repository: &(impl DeckRepository + UserRepository),
+    user: &UserId,
+    deck: &DeckId,
+    editor: &str,
+) -> Result<Deck, DeckError> {
+    let current = repository.read_deck(deck).await?;
+    if current.owner() != user {
+        return Err(DeckError::NotOwner);
+    }
+
+    let editor = match repository.read_user_by_name(editor).await {
+        Ok(editor) => editor,
+        Err(RepoReadError::NotFound) => return Err(DeckError::UnknownUser(editor.to_string())),
+        Err(e) => return Err(DeckError::Read(e)),
+    };
+
+    Ok(repository.add_editor(deck, editor.id()).await?)
+}
+
+#[instrument(skip_all, fields(user = %user, deck = %deck), err(level = "info"))]
+pub async fn subscribe(
+    repository: &impl DeckRepository,
+    user: &UserId,
+    deck: &DeckId,
+) -> Result<Deck, DeckError> {
+    require_login(user)?;
+
+    Ok(repository.subscribe(deck, user).await?)
+}
+
+#[instrument(skip_all, fields(user = %user, deck = %deck), err(level = "info"))]
+pub async fn unsubscribe(
+    repository: &impl DeckRepository,
+    user: &UserId,
+    deck: &DeckId,
+) -> Result<Deck, DeckError> {
+    Ok(repository.unsubscribe(deck, user).await?)
+}
+
+/// Resolves what `user` may read and write
+///
+/// Without `deck`, the user reads their own records together with those of all subscribed decks
+/// and writes their own. With `deck`, editors read and write the deck's records, subscribers
+/// only read them.
+#[instrument(skip_all, fields(user = %user), err(level = "info"))]
+pub async fn resolve_access(
+    repository: &impl DeckRepository,
+    user: &UserId,
+    deck: Option<&DeckId>,
+) -> Result<Access, DeckError> {
+    let Some(deck) = deck else {
+        let mut access = Access::own(user);
+        access.read.extend(
+            repository
+                .subscriptions(user)
+                .await?
+                .iter()
+                .map(Deck::vocabulary),
+        );
+        return Ok(access);
+    };
+
+    let deck = repository.read_deck(deck).await?;
+    let vocabulary = deck.vocabulary();
+    let write = if deck.can_edit(user) {
+        Some(vocabulary.clone())
+    } else if deck.is_subscribed(user) {
+        None
+    } else {
+        return Err(DeckError::NotSubscribed);
+    };
+
+    Ok(Access {
+        user: user.clone(),
+        read: vec![vocabulary],
+        write,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::domain::ports::TranslationRepository;
+    use crate::test_utils::{utils::shared::*, voci_repo_double::repo_double::VociRepoDouble};
+
+    #[actix_rt::test]
+    async fn own_access_includes_subscriptions() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        let deck = create_deck(&repo, &owner(), "Animals").await.unwrap();
+        let student = UserId::new(OTHER_OWNER);
+        subscribe(&repo, &student, deck.id()).await.unwrap();
+
+        let access = resolve_access(&repo, &student, None).await.unwrap();
+
+        assert_eq!(access.readable(), &[student.clone(), deck.vocabulary()]);
+        assert_eq!(access.writable(), Some(&student));
+    }
+
+    #[actix_rt::test]
+    async fn deck_access_by_role() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        let deck = create_deck(&repo, &owner(), "Animals").await.unwrap();
+        let student = UserId::new(OTHER_OWNER);
+
+        assert_eq!(
+            resolve_access(&repo, &student, Some(deck.id())).await,
+            Err(DeckError::NotSubscribed)
+        );
+
+        subscribe(&repo, &student, deck.id()).await.unwrap();
+        let subscriber = resolve_access(&repo, &student, Some(deck.id()))
+            .await
+            .unwrap();
+        let editor = resolve_access(&repo, &owner(), Some(deck.id()))
+            .await
+            .unwrap();
+
+        assert_eq!(subscriber.readable(), &[deck.vocabulary()]);
+        assert_eq!(subscriber.writable(), None);
+        assert_eq!(editor.writable(), Some(&deck.vocabulary()));
+    }
+
+    #[actix_rt::test]
+    async fn add_editor_only_by_owner() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        let deck = create_deck(&repo, &owner(), "Animals").await.unwrap();
+        let colleague = repo.create_user("carol", "hash").await.unwrap();
+
+        assert_eq!(
+            add_editor(&repo, colleague.id(), deck.id(), "carol").await,
+            Err(DeckError::NotOwner)
+        );
+        let deck = add_editor(&repo, &owner(), deck.id(), "carol")
+            .await
+            .unwrap();
+
+        assert!(deck.can_edit(colleague.id()));
+    }
+
+    #[actix_rt::test]
+    async fn anonymous_or_nameless_deck_error() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+
+        assert_eq!(
+            create_deck(&repo, &UserId::anonymous(), "Animals").await,
+            Err(DeckError::LoginRequired)
+        );
+        assert_eq!(
+            create_deck(&repo, &owner(), "  ").await,
+            Err(DeckError::InvalidName)
+        );
+    }
+}
@@ -1,7 +1,12 @@
 use thiserror::Error;
+use tracing::instrument;
 
-use crate::domain::ports::{RepoDeleteError, RepoReadError, TranslationRepository};
-use crate::domain::voci::{Lang, TranslationRecordError, Word};
+use crate::domain::deck::Access;
+use crate::domain::ports::{
+    RepoDeleteError, RepoReadError, RevisionRepository, TranslationRepository,
+};
+use crate::domain::revision::record_revision;
+use crate::domain::voci::{Lang, TranslationRecordError, Word, select_sense};
 
 #[derive(Debug, PartialEq, Error)]
 pub enum DeleteError {
@@ -11,18 +16,33 @@ pub enum DeleteError {
     Read(#[from] RepoReadError),
     #[error("Delete Error:")]
     Delete(#[from] RepoDeleteError),
+    #[error("Read-only access")]
+    ReadOnly,
 }
 
+/// Moves the record of a word, of `sense` if the word has several, to the trash
+///
+/// Its links are kept for a restore, see `trash::purge_trash`.
+#[instrument(skip_all, fields(access = %access, word = %word, lang = %lang), err(level = "info"))]
 pub async fn delete_translation(
-    repository: &impl TranslationRepository,
+    repository: &(impl TranslationRepository + RevisionRepository),
+    access: &Access,
     word: &str,
     lang: &Lang,
+    sense: Option<&str>,
 ) -> Result<(), DeleteError> {
     let word = Word::new(word, lang)?;
 
-    let tr_to_be_deleted = repository.read_by_word(&word).await?;
+    let senses = repository.read_by_word(access.readable(), &word).await?;
+    let tr_to_be_deleted = select_sense(senses, sense)?;
+    if !access.can_write(tr_to_be_deleted.owner()) {
+        return Err(DeleteError::ReadOnly);
+    }
 
-    repository.delete(tr_to_be_deleted.id()).await?;
+    repository
+        .delete(tr_to_be_deleted.owner(), tr_to_be_deleted.id())
+        .await?;
+    record_revision(repository, access, Some(tr_to_be_deleted), None).await;
 
     Ok(())
 }
@@ -31,22 +51,88 @@ pub async fn delete_translation(
 mod tests {
 
     use super::*;
+    use crate::domain::create_translation::create_translation;
+    use crate::domain::link::{Relation, link_translations, linked_records};
     use crate::test_utils::{utils::shared::*, voci_repo_double::repo_double::VociRepoDouble};
 
     #[actix_rt::test]
     async fn delete_ok_word_ok() {
         let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
 
-        let response = delete_translation(&repo, WORD, &WORD_LANG).await;
+        let response = delete_translation(&repo, &access(), WORD, &WORD_LANG, None).await;
 
         assert_eq!(response, Ok(()));
     }
 
+    #[actix_rt::test]
+    async fn delete_homograph_needs_known_sense() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        create_homographs(&repo).await;
+        let access = access();
+        let delete = |sense| delete_translation(&repo, &access, "avocat", &WORD_LANG, sense);
+
+        assert_eq!(
+            delete(None).await,
+            Err(DeleteError::Word(TranslationRecordError::AmbiguousSense))
+        );
+        assert_eq!(
+            delete(Some("judge")).await,
+            Err(DeleteError::Word(TranslationRecordError::UnknownSense(
+                "judge".to_string()
+            )))
+        );
+        assert_eq!(delete(Some("fruit")).await, Ok(()));
+        // The lawyer is the only sense left
+        assert_eq!(delete(None).await, Ok(()));
+        assert_eq!(
+            delete(None).await,
+            Err(DeleteError::Read(RepoReadError::NotFound))
+        );
+    }
+
+    #[actix_rt::test]
+    async fn delete_linked_record_link_hidden() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        let mut records = Vec::new();
+        for word in ["chat", "chatte"] {
+            let tr = create_translation(
+                &repo,
+                &access(),
+                word,
+                &WORD_LANG,
+                None,
+                &[word],
+                &TRANSLATION_LANG,
+            )
+            .await
+            .unwrap();
+            records.push(tr);
+        }
+        link_translations(
+            &repo,
+            &access(),
+            records[0].id(),
+            records[1].id(),
+            Relation::Family,
+        )
+        .await
+        .unwrap();
+
+        delete_translation(&repo, &access(), "chatte", &WORD_LANG, None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            linked_records(&repo, &access(), &records[..1]).await,
+            Ok(vec![])
+        );
+    }
+
     #[actix_rt::test]
     async fn delete_bad_word_err() {
         let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
 
-        let response = delete_translation(&repo, "", &WORD_LANG).await;
+        let response = delete_translation(&repo, &access(), "", &WORD_LANG, None).await;
 
         assert!(response.is_err());
         assert_eq!(
@@ -60,7 +146,7 @@ mod tests {
         let mut repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
         repo.set_error(true);
 
-        let response = delete_translation(&repo, WORD, &WORD_LANG).await;
+        let response = delete_translation(&repo, &access(), WORD, &WORD_LANG, None).await;
 
         assert!(response.is_err());
         assert_eq!(
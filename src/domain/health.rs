@@ -0,0 +1,41 @@
+use thiserror::Error;
+use tracing::instrument;
+
+use crate::domain::ports::{RepoHealthError, TranslationRepository};
+
+#[derive(Debug, PartialEq, Error)]
+pub enum HealthError {
+    #[error("Repository {0}")]
+    Repository(#[from] RepoHealthError),
+}
+
+/// Checks whether all dependencies needed to serve requests are available
+#[instrument(skip_all, err(level = "warn"))]
+pub async fn check_readiness(repository: &impl TranslationRepository) -> Result<(), HealthError> {
+    repository.health().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_utils::{utils::shared::*, voci_repo_double::repo_double::VociRepoDouble};
+
+    #[actix_rt::test]
+    async fn readiness_repository_up_ok() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+
+        assert_eq!(check_readiness(&repo).await, Ok(()));
+    }
+
+    #[actix_rt::test]
+    async fn readiness_repository_down_err() {
+        let mut repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        repo.set_error(true);
+
+        let result = check_readiness(&repo).await;
+
+        assert!(matches!(result, Err(HealthError::Repository(_))));
+    }
+}
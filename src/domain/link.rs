@@ -0,0 +1,233 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use thiserror::Error;
+use tracing::instrument;
+use utoipa::ToSchema;
+
+use crate::domain::deck::Access;
+use crate::domain::ports::{LinkRepository, RepoCreateError, RepoReadError, TranslationRepository};
+use crate::domain::voci::{TranslationId, TranslationRecord};
+
+/// How two TranslationRecords relate, each relation holds both ways
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Relation {
+    Synonym,
+    Antonym,
+    /// Words of the same family, e.g. "chien" and "chienne"
+    Family,
+}
+
+impl Relation {
+    /// Whether the translations of a related record are wrong answers, as opposed to synonyms
+    pub fn is_distractor(&self) -> bool {
+        !matches!(self, Relation::Synonym)
+    }
+}
+
+impl fmt::Display for Relation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Relation::Synonym => write!(f, "synonym"),
+            Relation::Antonym => write!(f, "antonym"),
+            Relation::Family => write!(f, "family"),
+        }
+    }
+}
+
+/// A relation between two TranslationRecords, kept by their IDs
+#[derive(Debug, Clone, PartialEq)]
+pub struct Link {
+    ends: [TranslationId; 2],
+    relation: Relation,
+}
+
+impl Link {
+    pub fn new(id: &TranslationId, other: &TranslationId, relation: Relation) -> Self {
+        Link {
+            ends: [id.clone(), other.clone()],
+            relation,
+        }
+    }
+
+    pub fn ends(&self) -> &[TranslationId; 2] {
+        &self.ends
+    }
+
+    pub fn relation(&self) -> Relation {
+        self.relation
+    }
+
+    /// The end opposite of `id`, `None` if `id` is no end of the link
+    pub fn other_end(&self, id: &TranslationId) -> Option<&TranslationId> {
+        match &self.ends {
+            [a, b] if a == id => Some(b),
+            [a, b] if b == id => Some(a),
+            _ => None,
+        }
+    }
+}
+
+/// A record linked to one of the records asked for
+#[derive(Debug, Clone, PartialEq)]
+pub struct Linked {
+    /// ID of the record asked for
+    pub from: TranslationId,
+    pub relation: Relation,
+    pub record: TranslationRecord,
+}
+
+#[derive(Debug, PartialEq, Error)]
+pub enum LinkError {
+    #[error("A record cannot be linked to itself")]
+    SelfLink,
+    #[error("Translation not found")]
+    NotFound,
+    #[error("Read-only access")]
+    ReadOnly,
+    #[error("Read Error: {0}")]
+    Read(#[from] RepoReadError),
+    #[error("Create Error")]
+    Create(#[from] RepoCreateError),
+}
+
+/// Links two records, both must exist and be writable
+#[instrument(skip_all, fields(access = %access, relation = %relation), err(level = "info"))]
+pub async fn link_translations(
+    repository: &(impl TranslationRepository + LinkRepository),
+    access: &Access,
+    id: &TranslationId,
+    other: &TranslationId,
+    relation: Relation,
+) -> Result<Link, LinkError> {
+    if id == other {
+        return Err(LinkError::SelfLink);
+    }
+
+    let ends = repository
+        .read_by_ids(access.readable(), &[id.clone(), other.clone()])
+        .await?;
+    if ends.len() < 2 {
+        return Err(LinkError::NotFound);
+    }
+    if !ends.iter().all(|tr| access.can_write(tr.owner())) {
+        return Err(LinkError::ReadOnly);
+    }
+
+    let link = Link::new(id, other, relation);
+    repository.add_link(&link).await?;
+    Ok(link)
+}
+
+/// Reads the records linked to any of `records`, links to records not readable are skipped
+#[instrument(skip_all, fields(access = %access, records = records.len()), err(level = "info"))]
+pub async fn linked_records(
+    repository: &(impl TranslationRepository + LinkRepository),
+    access: &Access,
+    records: &[TranslationRecord],
+) -> Result<Vec<Linked>, LinkError> {
+    let ids: Vec<TranslationId> = records
+        .iter()
+        .map(|tr| tr.id().clone())
+        .filter(|id| id.value().is_some())
+        .collect();
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let links = repository.links_of(&ids).await?;
+    let other_ids: Vec<TranslationId> = links
+        .iter()
+        .flat_map(|link| ids.iter().filter_map(|id| link.other_end(id)))
+        .cloned()
+        .collect();
+    let others = repository
+        .read_by_ids(access.readable(), &other_ids)
+        .await?;
+
+    Ok(links
+        .iter()
+        .flat_map(|link| {
+            ids.iter().filter_map(|from| {
+                let to = link.other_end(from)?;
+                let record = others.iter().find(|tr| tr.id() == to)?;
+                Some(Linked {
+                    from: from.clone(),
+                    relation: link.relation(),
+                    record: record.clone(),
+                })
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::domain::deck::{create_deck, resolve_access, subscribe};
+    use crate::domain::user::UserId;
+    use crate::test_utils::{utils::shared::*, voci_repo_double::repo_double::VociRepoDouble};
+
+    #[actix_rt::test]
+    async fn linked_records_found_from_both_ends() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        let chat = create_word(&repo, &access(), "chat").await;
+        let chatte = create_word(&repo, &access(), "chatte").await;
+
+        link_translations(&repo, &access(), chat.id(), chatte.id(), Relation::Family)
+            .await
+            .unwrap();
+        let of_chat = linked_records(&repo, &access(), std::slice::from_ref(&chat))
+            .await
+            .unwrap();
+        let of_chatte = linked_records(&repo, &access(), std::slice::from_ref(&chatte))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            of_chat,
+            vec![Linked {
+                from: chat.id().clone(),
+                relation: Relation::Family,
+                record: chatte.clone(),
+            }]
+        );
+        assert_eq!(of_chatte[0].record, chat);
+    }
+
+    #[actix_rt::test]
+    async fn link_to_itself_or_missing_record_err() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        let chat = create_word(&repo, &access(), "chat").await;
+        let missing = TranslationId::from("missing");
+
+        assert_eq!(
+            link_translations(&repo, &access(), chat.id(), chat.id(), Relation::Synonym).await,
+            Err(LinkError::SelfLink)
+        );
+        assert_eq!(
+            link_translations(&repo, &access(), chat.id(), &missing, Relation::Synonym).await,
+            Err(LinkError::NotFound)
+        );
+    }
+
+    #[actix_rt::test]
+    async fn link_records_of_subscribed_deck_read_only() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        let deck = create_deck(&repo, &owner(), "Animals").await.unwrap();
+        let editor = resolve_access(&repo, &owner(), Some(deck.id()))
+            .await
+            .unwrap();
+        let chat = create_word(&repo, &editor, "chat").await;
+        let chatte = create_word(&repo, &editor, "chatte").await;
+        let student = UserId::new(OTHER_OWNER);
+        subscribe(&repo, &student, deck.id()).await.unwrap();
+        let student = resolve_access(&repo, &student, None).await.unwrap();
+
+        let result =
+            link_translations(&repo, &student, chat.id(), chatte.id(), Relation::Family).await;
+
+        assert_eq!(result, Err(LinkError::ReadOnly));
+    }
+}
@@ -0,0 +1,50 @@
+use thiserror::Error;
+use tracing::instrument;
+
+use crate::domain::deck::Access;
+use crate::domain::ports::{RepoReadError, TranslationRepository};
+use crate::domain::voci::TranslationRecord;
+
+#[derive(Debug, PartialEq, Error)]
+pub enum ListError {
+    #[error("Unknown")]
+    Unknown,
+}
+
+#[instrument(skip_all, fields(access = %access), err)]
+pub async fn list_translations(
+    repository: &impl TranslationRepository,
+    access: &Access,
+) -> Result<Vec<TranslationRecord>, ListError> {
+    let result = repository.list(access.readable()).await;
+
+    result.map_err(|e| match e {
+        RepoReadError::NotFound | RepoReadError::Unknown => ListError::Unknown,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::test_utils::{utils::shared::*, voci_repo_double::repo_double::VociRepoDouble};
+
+    #[actix_rt::test]
+    async fn list_all_records() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+
+        let records = list_translations(&repo, &access()).await;
+
+        assert_eq!(records.unwrap(), vec![stub_translation_record(false)]);
+    }
+
+    #[actix_rt::test]
+    async fn list_manually_provoked_error_err() {
+        let mut repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        repo.set_error(true);
+
+        let records = list_translations(&repo, &access()).await;
+
+        assert_eq!(records.unwrap_err(), ListError::Unknown);
+    }
+}
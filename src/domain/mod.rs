@@ -1,9 +1,22 @@
 /// Domain
+pub mod auth;
+pub mod conjugation;
+pub mod deck;
+pub mod link;
+pub mod revision;
+pub mod text;
+pub mod user;
 pub mod voci;
 
 /// Use Cases
+pub mod account;
 pub mod create_translation;
 pub mod delete_translation;
+pub mod health;
+pub mod list_translations;
 pub mod ports;
+pub mod quiz;
 pub mod read_translation;
+pub mod search_translations;
+pub mod trash;
 pub mod update_translation;
@@ -1,11 +1,19 @@
 use async_trait::async_trait;
+use std::time::SystemTime;
 use thiserror::Error;
 
 use crate::config::PersistenceConfig;
-use crate::domain::voci::{TranslationId, TranslationRecord, Word};
+use crate::domain::deck::{Deck, DeckId};
+use crate::domain::link::Link;
+use crate::domain::quiz::Progress;
+use crate::domain::revision::Revision;
+use crate::domain::user::{User, UserId};
+use crate::domain::voci::{Lang, TranslationId, TranslationRecord, Word};
 
 #[derive(Error, Debug, PartialEq)]
 pub enum RepoCreateError {
+    #[error("Already exists")]
+    Duplicate,
     #[error("Unknown")]
     Unknown,
 }
@@ -38,6 +46,13 @@ pub enum RepoDeleteError {
     Unknown,
 }
 
+#[derive(Error, Debug, PartialEq)]
+pub enum RepoHealthError {
+    #[error("Unavailable: {0}")]
+    Unavailable(String),
+}
+
+/// Repository of TranslationRecords, reads and updates leave the records in the trash out
 #[async_trait]
 pub trait TranslationRepository: Send + Sync + Clone + 'static {
     /// Creation of a repository
@@ -48,15 +63,139 @@ pub trait TranslationRepository: Send + Sync + Clone + 'static {
     /// Insert the received TranslationRecord in the persistence system
     async fn create(&self, tr: &TranslationRecord) -> Result<TranslationRecord, RepoCreateError>;
 
-    /// Read/find a TranslationRecord given a Word
-    async fn read_by_word(&self, word: &Word) -> Result<TranslationRecord, RepoReadError>;
+    /// Read/find the TranslationRecords of any of `owners` given a Word, one per sense
+    ///
+    /// If several owners have records of the Word, those of the first owner win.
+    /// `NotFound` if no owner has a record of the Word.
+    async fn read_by_word(
+        &self,
+        owners: &[UserId],
+        word: &Word,
+    ) -> Result<Vec<TranslationRecord>, RepoReadError>;
+
+    /// Read the TranslationRecords of any of `owners` given their IDs, unknown IDs are skipped
+    async fn read_by_ids(
+        &self,
+        owners: &[UserId],
+        ids: &[TranslationId],
+    ) -> Result<Vec<TranslationRecord>, RepoReadError>;
+
+    /// Read all TranslationRecords of all `owners` in the persistence system
+    async fn list(&self, owners: &[UserId]) -> Result<Vec<TranslationRecord>, RepoReadError>;
+
+    /// Read the words of `lang` of all `owners` along with their search keys, not the records
+    async fn words(
+        &self,
+        owners: &[UserId],
+        lang: &Lang,
+    ) -> Result<Vec<(String, String)>, RepoReadError>;
+
+    /// Read the TranslationRecords of all `owners` whose word or one of its translations
+    /// starts with `key`, a search key as of `text::search_key`
+    ///
+    /// With `lang`, only words or translations of that language are matched. With `anywhere`,
+    /// words or translations containing `key` match as well. That cannot seek an index and is
+    /// much slower on large vocabularies.
+    async fn search(
+        &self,
+        owners: &[UserId],
+        key: &str,
+        lang: Option<&Lang>,
+        anywhere: bool,
+    ) -> Result<Vec<TranslationRecord>, RepoReadError>;
 
     /// Update a TranslationRecord given a TranslationRecord
     ///
-    /// The TranslationId in the argument is used to identify the TranslationRecord.
+    /// The TranslationId and owner in the argument are used to identify the TranslationRecord.
     /// Translations within it, are used to update the existing translations
     async fn update(&self, tr: &TranslationRecord) -> Result<TranslationRecord, RepoUpdateError>;
 
-    /// Delete a TranslationRecord given an ID
-    async fn delete(&self, id: &TranslationId) -> Result<(), RepoDeleteError>;
+    /// Move a TranslationRecord of `owner` given an ID to the trash, stamped with the time of
+    /// deletion
+    async fn delete(&self, owner: &UserId, id: &TranslationId) -> Result<(), RepoDeleteError>;
+
+    /// Read the TranslationRecords of all `owners` in the trash
+    async fn trash(&self, owners: &[UserId]) -> Result<Vec<TranslationRecord>, RepoReadError>;
+
+    /// Take a TranslationRecord of `owner` given an ID out of the trash
+    async fn restore(
+        &self,
+        owner: &UserId,
+        id: &TranslationId,
+    ) -> Result<TranslationRecord, RepoUpdateError>;
+
+    /// Delete the TranslationRecords moved to the trash before `deleted_before` for good
+    ///
+    /// Returns the IDs of the records actually deleted, records restored meanwhile are left out.
+    async fn purge(
+        &self,
+        deleted_before: SystemTime,
+    ) -> Result<Vec<TranslationId>, RepoDeleteError>;
+
+    /// Check that the persistence system is reachable and answers
+    async fn health(&self) -> Result<(), RepoHealthError>;
+}
+
+#[async_trait]
+pub trait UserRepository: Send + Sync + Clone + 'static {
+    /// Insert a user, the persistence system assigns its id
+    ///
+    /// `Duplicate` if a user of that name exists.
+    async fn create_user(&self, name: &str, password_hash: &str) -> Result<User, RepoCreateError>;
+
+    /// Read/find a User given its unique name
+    async fn read_user_by_name(&self, name: &str) -> Result<User, RepoReadError>;
+}
+
+#[async_trait]
+pub trait DeckRepository: Send + Sync + Clone + 'static {
+    /// Insert a deck without editors and subscribers, the persistence system assigns its id
+    async fn create_deck(&self, name: &str, owner: &UserId) -> Result<Deck, RepoCreateError>;
+
+    /// Read/find a Deck given its id
+    async fn read_deck(&self, id: &DeckId) -> Result<Deck, RepoReadError>;
+
+    /// Add `editor` to the editors of a deck, adding an editor twice is a no-op
+    async fn add_editor(&self, id: &DeckId, editor: &UserId) -> Result<Deck, RepoUpdateError>;
+
+    /// Add `user` to the subscribers of a deck, subscribing twice is a no-op
+    async fn subscribe(&self, id: &DeckId, user: &UserId) -> Result<Deck, RepoUpdateError>;
+
+    /// Remove `user` from the subscribers of a deck
+    async fn unsubscribe(&self, id: &DeckId, user: &UserId) -> Result<Deck, RepoUpdateError>;
+
+    /// Read all Decks `user` is subscribed to
+    async fn subscriptions(&self, user: &UserId) -> Result<Vec<Deck>, RepoReadError>;
+}
+
+#[async_trait]
+pub trait ProgressRepository: Send + Sync + Clone + 'static {
+    /// Count an answer of `user` for a TranslationRecord and return the updated progress
+    async fn record_answer(
+        &self,
+        user: &UserId,
+        id: &TranslationId,
+        correct: bool,
+    ) -> Result<Progress, RepoUpdateError>;
+}
+
+#[async_trait]
+pub trait LinkRepository: Send + Sync + Clone + 'static {
+    /// Insert a link between two TranslationRecords, adding a link twice is a no-op
+    async fn add_link(&self, link: &Link) -> Result<(), RepoCreateError>;
+
+    /// Read all links from or to any of `ids`
+    async fn links_of(&self, ids: &[TranslationId]) -> Result<Vec<Link>, RepoReadError>;
+
+    /// Delete all links from or to `id`
+    async fn remove_links(&self, id: &TranslationId) -> Result<(), RepoDeleteError>;
+}
+
+#[async_trait]
+pub trait RevisionRepository: Send + Sync + Clone + 'static {
+    /// Append a revision, the persistence system assigns its id; revisions are never changed
+    async fn add_revision(&self, revision: &Revision) -> Result<Revision, RepoCreateError>;
+
+    /// Read all revisions of a TranslationRecord given its ID, oldest first
+    async fn revisions_of(&self, id: &TranslationId) -> Result<Vec<Revision>, RepoReadError>;
 }
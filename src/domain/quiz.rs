@@ -0,0 +1,521 @@
+use rand::seq::{IndexedRandom, SliceRandom};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::instrument;
+use utoipa::ToSchema;
+
+use crate::domain::conjugation::{Person, Tense};
+use crate::domain::deck::Access;
+use crate::domain::link::{self, Linked};
+use crate::domain::ports::{
+    LinkRepository, ProgressRepository, RepoReadError, TranslationRepository,
+};
+use crate::domain::text;
+use crate::domain::voci::{Lang, TranslationRecord, TranslationRecordError, Word, select_sense};
+use crate::telemetry;
+
+#[derive(Debug, PartialEq, Error)]
+pub enum QuizError {
+    #[error("Bad Input: {0}")]
+    Word(#[from] TranslationRecordError),
+    #[error("Translation not found")]
+    RecordNotFound,
+    #[error("Nothing to practice")]
+    Empty,
+    #[error("No conjugation of the verb in that tense")]
+    NotConjugated,
+    #[error("Unknown")]
+    Unknown,
+}
+
+impl From<RepoReadError> for QuizError {
+    fn from(e: RepoReadError) -> Self {
+        match e {
+            RepoReadError::NotFound => QuizError::RecordNotFound,
+            RepoReadError::Unknown => QuizError::Unknown,
+        }
+    }
+}
+
+/// Answers a user gave for a TranslationRecord so far
+///
+/// Kept per user, so subscribers of a deck each have their own.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Progress {
+    pub correct: u32,
+    pub incorrect: u32,
+}
+
+/// A word to practice
+#[derive(Debug, Clone, PartialEq)]
+pub struct Question {
+    pub record: TranslationRecord,
+    /// Wrong answers close to the right ones, the translations of antonyms and related words
+    pub distractors: Vec<String>,
+}
+
+/// Outcome of a single quiz answer
+#[derive(Debug, PartialEq)]
+pub struct Grade {
+    pub correct: bool,
+    pub translations: Vec<String>,
+    /// Progress including this answer
+    pub progress: Progress,
+}
+
+/// A verb form to practice, e.g. "aller, présent, nous"
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConjugationQuestion {
+    pub word: String,
+    pub sense: Option<String>,
+    pub tense: Tense,
+    pub person: Person,
+}
+
+impl ConjugationQuestion {
+    pub fn prompt(&self) -> String {
+        format!("{}, {}, {}", self.word, self.tense, self.person)
+    }
+}
+
+/// Outcome of a conjugation answer
+#[derive(Debug, PartialEq)]
+pub struct ConjugationGrade {
+    pub correct: bool,
+    pub expected: String,
+}
+
+/// Draws up to `size` randomly ordered TranslationRecords to practice
+///
+/// Distractors are a courtesy, failing to read the links yields none.
+///
+/// # Errors
+/// Returns `QuizError::Empty` if there is nothing to practice
+#[instrument(skip_all, fields(access = %access, size = size), err(level = "info"))]
+pub async fn draw_quiz(
+    repository: &(impl TranslationRepository + LinkRepository),
+    access: &Access,
+    size: usize,
+) -> Result<Vec<Question>, QuizError> {
+    let mut records = repository.list(access.readable()).await?;
+
+    if records.is_empty() {
+        return Err(QuizError::Empty);
+    }
+
+    records.shuffle(&mut rand::rng());
+    records.truncate(size);
+
+    let linked = link::linked_records(repository, access, &records)
+        .await
+        .unwrap_or_default();
+
+    Ok(records
+        .into_iter()
+        .map(|record| Question {
+            distractors: distractors(&record, &linked),
+            record,
+        })
+        .collect())
+}
+
+/// Translations of the antonyms and related words of `record`, except its own ones
+fn distractors(record: &TranslationRecord, linked: &[Linked]) -> Vec<String> {
+    let (_, _, _, translations, translation_lang) = record.flat();
+    let answers: Vec<String> = translations.iter().map(|t| text::search_key(t)).collect();
+
+    let mut distractors: Vec<String> = Vec::new();
+    let related = linked
+        .iter()
+        .filter(|l| l.from == *record.id() && l.relation.is_distractor())
+        .map(|l| l.record.flat())
+        .filter(|(_, _, _, _, lang)| *lang == translation_lang);
+    for (_, _, _, wrong, _) in related {
+        for w in wrong {
+            if !answers.contains(&text::search_key(w)) && !distractors.contains(w) {
+                distractors.push(w.clone());
+            }
+        }
+    }
+    distractors
+}
+
+/// Grades an answer against the translations of a TranslationRecord
+///
+/// Whitespace, Unicode normalization and letter case are ignored, accents are not.
+/// Leading articles may be left out, unless `require_article` is set: then an answer must
+/// carry the article of a translation that has one.
+pub fn grade(tr: &TranslationRecord, answer: &str, require_article: bool) -> Grade {
+    let (_, _, _, translations, lang) = tr.flat();
+    let answer = text::canonical(answer).to_lowercase();
+    let (answer_article, answer) = lang.split_article(&answer);
+
+    let matches = |translation: &String| {
+        let translation = translation.to_lowercase();
+        let (article, translation) = lang.split_article(&translation);
+        let article_ok = !require_article
+            || article.is_none_or(|a| answer_article.is_some_and(|b| a.text == b.text));
+        translation == answer && article_ok
+    };
+
+    Grade {
+        correct: translations.iter().any(matches),
+        translations: translations.clone(),
+        progress: Progress::default(),
+    }
+}
+
+/// Draws a random form of a random verb that has a conjugation table
+///
+/// # Errors
+/// Returns `QuizError::Empty` if no readable verb is conjugated
+#[instrument(skip_all, fields(access = %access), err(level = "info"))]
+pub async fn draw_conjugation(
+    repository: &impl TranslationRepository,
+    access: &Access,
+) -> Result<ConjugationQuestion, QuizError> {
+    let records = repository.list(access.readable()).await?;
+    let verbs: Vec<&TranslationRecord> = records
+        .iter()
+        .filter(|tr| tr.conjugation().is_some())
+        .collect();
+
+    let mut rng = rand::rng();
+    let verb = verbs.choose(&mut rng).ok_or(QuizError::Empty)?;
+    let tense = verb
+        .conjugation()
+        .and_then(|c| c.tenses().choose(&mut rng))
+        .ok_or(QuizError::Empty)?
+        .tense;
+
+    Ok(ConjugationQuestion {
+        word: verb.word().value().0.clone(),
+        sense: verb.sense().map(str::to_string),
+        tense,
+        person: *Person::ALL.choose(&mut rng).unwrap(),
+    })
+}
+
+/// Grades a verb form, the subject pronoun may be given along, e.g. "nous allons"
+///
+/// Whitespace, Unicode normalization and letter case are ignored, accents are not.
+pub fn grade_conjugation(expected: &str, person: Person, answer: &str) -> ConjugationGrade {
+    let answer = text::canonical(answer).to_lowercase();
+    let pronouns: &[&str] = match person {
+        Person::Je => &["je ", "j'", "j\u{2019}"],
+        Person::Tu => &["tu "],
+        Person::Il => &["il ", "elle ", "on "],
+        Person::Nous => &["nous "],
+        Person::Vous => &["vous "],
+        Person::Ils => &["ils ", "elles "],
+    };
+    let form = pronouns
+        .iter()
+        .find_map(|p| answer.strip_prefix(p))
+        .unwrap_or(&answer);
+
+    ConjugationGrade {
+        correct: form == expected.to_lowercase(),
+        expected: expected.to_string(),
+    }
+}
+
+/// Looks up the form of a French verb and grades the answer given for it
+#[instrument(skip_all, fields(access = %access, word = %word, tense = %tense, person = %person), err(level = "info"))]
+pub async fn check_conjugation(
+    repository: &impl TranslationRepository,
+    access: &Access,
+    word: &str,
+    sense: Option<&str>,
+    tense: Tense,
+    person: Person,
+    answer: &str,
+) -> Result<ConjugationGrade, QuizError> {
+    let word = Word::new(word, &Lang::fr)?;
+
+    let senses = repository.read_by_word(access.readable(), &word).await?;
+    let tr = select_sense(senses, sense)?;
+    let expected = tr
+        .conjugation()
+        .and_then(|c| c.form(tense, person))
+        .ok_or(QuizError::NotConjugated)?;
+
+    Ok(grade_conjugation(expected, person, answer))
+}
+
+/// Looks up a word, grades the answer given for it and counts it in the user's progress
+///
+/// Answers are graded against every sense of the word, progress counts for the sense matched.
+#[instrument(skip_all, fields(access = %access, word = %word, lang = %lang), err(level = "info"))]
+pub async fn check_answer(
+    repository: &(impl TranslationRepository + ProgressRepository),
+    access: &Access,
+    word: &str,
+    lang: &Lang,
+    answer: &str,
+    require_article: bool,
+) -> Result<Grade, QuizError> {
+    let word = Word::new(word, lang)?;
+
+    let senses = repository.read_by_word(access.readable(), &word).await?;
+    let first = senses.first().ok_or(QuizError::RecordNotFound)?;
+    let (tr, mut grade) = senses
+        .iter()
+        .map(|tr| (tr, grade(tr, answer, require_article)))
+        .find(|(_, g)| g.correct)
+        .unwrap_or_else(|| (first, grade(first, answer, require_article)));
+    telemetry::quiz_answered(grade.correct);
+    grade.progress = repository
+        .record_answer(access.user(), tr.id(), grade.correct)
+        .await
+        .map_err(|_| QuizError::Unknown)?;
+    Ok(grade)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::domain::conjugation::{Table, conjugate_translation};
+    use crate::domain::create_translation::create_translation;
+    use crate::domain::deck::{create_deck, resolve_access, subscribe};
+    use crate::domain::link::Relation;
+    use crate::domain::user::UserId;
+    use crate::test_utils::{utils::shared::*, voci_repo_double::repo_double::VociRepoDouble};
+
+    #[actix_rt::test]
+    async fn draw_quiz_limited_to_available_records() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+
+        let questions = draw_quiz(&repo, &access(), 10).await.unwrap();
+
+        assert_eq!(
+            questions,
+            vec![Question {
+                record: stub_translation_record(false),
+                distractors: vec![],
+            }]
+        );
+    }
+
+    #[actix_rt::test]
+    async fn draw_quiz_antonyms_distract_synonyms_not() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        let other = Access::own(&UserId::new(OTHER_OWNER));
+        let create = |word: &'static str, translations: &'static [&'static str]| {
+            create_translation(
+                &repo,
+                &other,
+                word,
+                &WORD_LANG,
+                None,
+                translations,
+                &TRANSLATION_LANG,
+            )
+        };
+        let grand = create("grand", &["groß"]).await.unwrap();
+        let petit = create("petit", &["klein", "groß"]).await.unwrap();
+        let immense = create("immense", &["riesig"]).await.unwrap();
+        link::link_translations(&repo, &other, grand.id(), petit.id(), Relation::Antonym)
+            .await
+            .unwrap();
+        link::link_translations(&repo, &other, grand.id(), immense.id(), Relation::Synonym)
+            .await
+            .unwrap();
+
+        let questions = draw_quiz(&repo, &other, 10).await.unwrap();
+
+        let grand = questions.iter().find(|q| q.record == grand).unwrap();
+        assert_eq!(grand.distractors, ["klein"]);
+    }
+
+    #[actix_rt::test]
+    async fn draw_quiz_manually_provoked_error_err() {
+        let mut repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        repo.set_error(true);
+
+        let records = draw_quiz(&repo, &access(), 10).await;
+
+        assert_eq!(records.unwrap_err(), QuizError::Unknown);
+    }
+
+    #[test]
+    fn grade_known_translation_correct() {
+        let tr = stub_translation_record(false);
+
+        let grade = grade(&tr, " Hund ", false);
+
+        assert!(grade.correct);
+        assert_on_translations(
+            &grade.translations,
+            TRANSLATIONS.map(|t| t.to_string()).as_ref(),
+        );
+    }
+
+    #[test]
+    fn grade_unknown_translation_incorrect() {
+        let tr = stub_translation_record(false);
+
+        let grade = grade(&tr, "katze", false);
+
+        assert!(!grade.correct);
+    }
+
+    #[test]
+    fn grade_article_optional_unless_required() {
+        let tr = TranslationRecord::new(
+            None,
+            &UserId::new(OWNER),
+            "le chien",
+            &Lang::fr,
+            &["der Hund", "Köter"],
+            &Lang::de,
+        )
+        .unwrap();
+
+        assert!(grade(&tr, "hund", false).correct);
+        assert!(grade(&tr, "Der Hund", false).correct);
+        assert!(grade(&tr, "das Hund", false).correct);
+        assert!(grade(&tr, "der hund", true).correct);
+        assert!(!grade(&tr, "hund", true).correct);
+        assert!(!grade(&tr, "die Hund", true).correct);
+        assert!(grade(&tr, "köter", true).correct);
+    }
+
+    #[actix_rt::test]
+    async fn check_answer_bad_word_err() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+
+        let grade = check_answer(&repo, &access(), "", &WORD_LANG, "hund", false).await;
+
+        assert_eq!(
+            grade.unwrap_err(),
+            QuizError::Word(TranslationRecordError::EmptyWord)
+        );
+    }
+
+    #[actix_rt::test]
+    async fn check_answer_existing_word_graded() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+
+        let grade = check_answer(&repo, &access(), WORD, &WORD_LANG, TRANSLATIONS[1], false).await;
+
+        assert!(grade.unwrap().correct);
+    }
+
+    #[actix_rt::test]
+    async fn check_answer_any_sense_correct() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        create_translation(
+            &repo,
+            &access(),
+            WORD,
+            &WORD_LANG,
+            Some("slang"),
+            &["Bulle"],
+            &TRANSLATION_LANG,
+        )
+        .await
+        .unwrap();
+
+        let stub_sense = check_answer(&repo, &access(), WORD, &WORD_LANG, "hund", false).await;
+        let slang = check_answer(&repo, &access(), WORD, &WORD_LANG, "bulle", false).await;
+
+        assert!(stub_sense.unwrap().correct);
+        assert_eq!(slang.unwrap().translations, ["Bulle"]);
+    }
+
+    #[actix_rt::test]
+    async fn check_answer_progress_kept_per_user() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        let deck = create_deck(&repo, &owner(), "Animals").await.unwrap();
+        let student = UserId::new(OTHER_OWNER);
+        subscribe(&repo, &student, deck.id()).await.unwrap();
+        let student = resolve_access(&repo, &student, None).await.unwrap();
+
+        check_answer(&repo, &access(), WORD, &WORD_LANG, "katze", false)
+            .await
+            .unwrap();
+        let mine = check_answer(&repo, &access(), WORD, &WORD_LANG, TRANSLATIONS[0], false)
+            .await
+            .unwrap();
+        let theirs = check_answer(&repo, &student, WORD, &WORD_LANG, TRANSLATIONS[0], false)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            mine.progress,
+            Progress {
+                correct: 1,
+                incorrect: 1
+            }
+        );
+        assert_eq!(
+            theirs.progress,
+            Progress {
+                correct: 1,
+                incorrect: 0
+            }
+        );
+    }
+
+    #[test]
+    fn grade_conjugation_pronoun_optional() {
+        assert!(grade_conjugation("allons", Person::Nous, " Nous  allons").correct);
+        assert!(grade_conjugation("allons", Person::Nous, "allons").correct);
+        assert!(grade_conjugation("aime", Person::Je, "j'aime").correct);
+        assert!(grade_conjugation("va", Person::Il, "elle va").correct);
+        assert!(!grade_conjugation("vais", Person::Je, "vas").correct);
+        assert!(!grade_conjugation("préfère", Person::Je, "prefere").correct);
+    }
+
+    #[actix_rt::test]
+    async fn draw_and_check_conjugation_of_conjugated_verb() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        assert_eq!(
+            draw_conjugation(&repo, &access()).await,
+            Err(QuizError::Empty)
+        );
+        create_translation(
+            &repo,
+            &access(),
+            "finir",
+            &Lang::fr,
+            None,
+            &["beenden"],
+            &Lang::de,
+        )
+        .await
+        .unwrap();
+        conjugate_translation(&repo, &access(), "finir", None, Table::Regular, &[])
+            .await
+            .unwrap();
+
+        let question = draw_conjugation(&repo, &access()).await.unwrap();
+        let right = check_conjugation(
+            &repo,
+            &access(),
+            "finir",
+            None,
+            Tense::Present,
+            Person::Nous,
+            "finissons",
+        )
+        .await
+        .unwrap();
+        let unconjugated = check_conjugation(
+            &repo,
+            &access(),
+            WORD,
+            None,
+            Tense::Present,
+            Person::Nous,
+            "chiennons",
+        )
+        .await;
+
+        assert_eq!(question.word, "finir");
+        assert!(right.correct);
+        assert_eq!(unconjugated, Err(QuizError::NotConjugated));
+    }
+}
@@ -1,53 +1,141 @@
 use thiserror::Error;
+use tracing::instrument;
 
+use crate::domain::deck::Access;
 use crate::domain::ports::{RepoReadError, TranslationRepository};
+use crate::domain::text;
 use crate::domain::voci::{Lang, TranslationRecord, TranslationRecordError, Word};
 
+/// Most suggestions offered for a word not found
+pub const MAX_SUGGESTIONS: usize = 5;
+/// Words further away than this are not worth suggesting
+const MAX_SUGGESTION_DISTANCE: usize = 3;
+
 #[derive(Debug, PartialEq, Error)]
 pub enum ReadError {
     #[error("Bad Input: {0}")]
     QueryWord(#[from] TranslationRecordError),
-    #[error("Translation not found")]
-    RecordNotFound,
+    /// Carries the nearest existing words, see `suggestions`
+    #[error("Translation not found{}", did_you_mean(.0))]
+    RecordNotFound(Vec<String>),
     #[error("Unknown")]
     Unknown,
 }
 
+fn did_you_mean(suggestions: &[String]) -> String {
+    if suggestions.is_empty() {
+        String::new()
+    } else {
+        format!(", did you mean {}?", suggestions.join(", "))
+    }
+}
+
+/// Reads the records of all senses of a word, or only that of `sense` if given
+///
+/// The user's own records win over those of subscribed decks.
+#[instrument(skip_all, fields(access = %access, word = %word, lang = %lang), err(level = "info"))]
 pub async fn read_translation(
     repository: &impl TranslationRepository,
+    access: &Access,
     word: &str,
     lang: &Lang,
-) -> Result<TranslationRecord, ReadError> {
+    sense: Option<&str>,
+) -> Result<Vec<TranslationRecord>, ReadError> {
     let word = Word::new(word, lang)?;
 
-    let result = repository.read_by_word(&word).await;
+    let result = repository.read_by_word(access.readable(), &word).await;
+
+    match result {
+        Ok(mut senses) => {
+            if sense.is_some() {
+                senses.retain(|tr| tr.is_sense(sense));
+            }
+            if senses.is_empty() {
+                return Err(ReadError::RecordNotFound(Vec::new()));
+            }
+            Ok(senses)
+        }
+        Err(RepoReadError::NotFound) => Err(ReadError::RecordNotFound(
+            suggestions(repository, access, &word).await,
+        )),
+        Err(RepoReadError::Unknown) => Err(ReadError::Unknown),
+    }
+}
+
+/// Up to `MAX_SUGGESTIONS` readable words of the same language nearest to `word`
+///
+/// Words are compared by the edit distance of their search keys, nearest first. Suggestions
+/// are a courtesy, failing to list the words yields none.
+pub async fn suggestions(
+    repository: &impl TranslationRepository,
+    access: &Access,
+    word: &Word,
+) -> Vec<String> {
+    let (_, lang) = word.value();
+    let key = word.search_key();
+    let Ok(words) = repository.words(access.readable(), lang).await else {
+        return Vec::new();
+    };
+
+    let mut nearest: Vec<(usize, String)> = words
+        .into_iter()
+        .map(|(w, k)| (text::edit_distance(&key, &k), w))
+        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .collect();
+    nearest.sort();
+    nearest.dedup_by(|a, b| a.1 == b.1);
 
-    result.map_err(|e| match e {
-        RepoReadError::NotFound => ReadError::RecordNotFound,
-        RepoReadError::Unknown => ReadError::Unknown,
-    })
+    nearest
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, w)| w)
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    use crate::domain::deck::{create_deck, resolve_access, subscribe};
+    use crate::domain::user::UserId;
     use crate::test_utils::{utils::shared::*, voci_repo_double::repo_double::VociRepoDouble};
 
     #[actix_rt::test]
     async fn read_well_formatted_word() {
         let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
 
-        let read_trans = read_translation(&repo, WORD, &WORD_LANG).await;
+        let read_trans = read_translation(&repo, &access(), WORD, &WORD_LANG, None).await;
 
-        assert_eq!(stub_translation_record(false), read_trans.unwrap())
+        assert_eq!(vec![stub_translation_record(false)], read_trans.unwrap())
+    }
+
+    #[actix_rt::test]
+    async fn read_homograph_all_or_named_sense() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        create_homographs(&repo).await;
+
+        let all = read_translation(&repo, &access(), "avocat", &WORD_LANG, None)
+            .await
+            .unwrap();
+        let fruit = read_translation(&repo, &access(), "avocat", &WORD_LANG, Some("Fruit"))
+            .await
+            .unwrap();
+        let judge = read_translation(&repo, &access(), "avocat", &WORD_LANG, Some("judge")).await;
+
+        assert_eq!(
+            all.iter().map(|tr| tr.sense()).collect::<Vec<_>>(),
+            [Some("lawyer"), Some("fruit")]
+        );
+        assert_eq!(fruit.len(), 1);
+        assert_eq!(fruit[0].flat().3, &["Avocado"]);
+        assert_eq!(judge.unwrap_err(), ReadError::RecordNotFound(vec![]));
     }
 
     #[actix_rt::test]
     async fn read_badly_formatted_word_err() {
         let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
 
-        let read_trans = read_translation(&repo, "", &WORD_LANG).await;
+        let read_trans = read_translation(&repo, &access(), "", &WORD_LANG, None).await;
 
         assert!(read_trans.is_err());
         assert_eq!(
@@ -55,4 +143,47 @@ mod tests {
             ReadError::QueryWord(TranslationRecordError::EmptyWord)
         );
     }
+
+    #[actix_rt::test]
+    async fn read_misspelled_word_nearest_suggested() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+
+        let misspelled = read_translation(&repo, &access(), "Chein", &WORD_LANG, None).await;
+        let unrelated = read_translation(&repo, &access(), "ordinateur", &WORD_LANG, None).await;
+        let other_lang = read_translation(&repo, &access(), "chein", &Lang::de, None).await;
+
+        assert_eq!(
+            misspelled.unwrap_err(),
+            ReadError::RecordNotFound(vec![WORD.to_string()])
+        );
+        assert_eq!(unrelated.unwrap_err(), ReadError::RecordNotFound(vec![]));
+        assert_eq!(other_lang.unwrap_err(), ReadError::RecordNotFound(vec![]));
+    }
+
+    #[test]
+    fn record_not_found_message_lists_suggestions() {
+        let e = ReadError::RecordNotFound(vec!["chien".to_string(), "chine".to_string()]);
+
+        assert_eq!(
+            e.to_string(),
+            "Translation not found, did you mean chien, chine?"
+        );
+        assert_eq!(
+            ReadError::RecordNotFound(vec![]).to_string(),
+            "Translation not found"
+        );
+    }
+
+    #[actix_rt::test]
+    async fn read_record_of_subscribed_deck() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        let deck = create_deck(&repo, &owner(), "Animals").await.unwrap();
+        let student = UserId::new(OTHER_OWNER);
+        subscribe(&repo, &student, deck.id()).await.unwrap();
+        let student = resolve_access(&repo, &student, None).await.unwrap();
+
+        let read_trans = read_translation(&repo, &student, WORD, &WORD_LANG, None).await;
+
+        assert_eq!(read_trans.unwrap()[0].owner(), &deck.vocabulary());
+    }
 }
@@ -0,0 +1,309 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::SystemTime;
+use thiserror::Error;
+use tracing::instrument;
+use utoipa::ToSchema;
+
+use crate::domain::deck::Access;
+use crate::domain::ports::{
+    RepoReadError, RepoUpdateError, RevisionRepository, TranslationRepository,
+};
+use crate::domain::user::UserId;
+use crate::domain::voci::{TranslationId, TranslationRecord};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RevisionId(String);
+
+impl RevisionId {
+    pub fn new(id: &str) -> Self {
+        RevisionId(id.to_string())
+    }
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+/// What a Revision did to its TranslationRecord
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Change {
+    Created,
+    Updated,
+    /// Moved to the trash
+    Deleted,
+    /// Taken out of the trash
+    Restored,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Created => write!(f, "created"),
+            Change::Updated => write!(f, "updated"),
+            Change::Deleted => write!(f, "deleted"),
+            Change::Restored => write!(f, "restored"),
+        }
+    }
+}
+
+/// An immutable entry of the history of a TranslationRecord, the record before and after a
+/// change done by `actor`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Revision {
+    /// Assigned by the persistence system
+    id: Option<RevisionId>,
+    record: TranslationId,
+    actor: UserId,
+    at: SystemTime,
+    before: Option<TranslationRecord>,
+    after: Option<TranslationRecord>,
+}
+
+impl Revision {
+    /// A revision done now, `before` is `None` for a creation and `after` for a deletion
+    pub fn new(
+        actor: &UserId,
+        before: Option<TranslationRecord>,
+        after: Option<TranslationRecord>,
+    ) -> Self {
+        let record = after
+            .as_ref()
+            .or(before.as_ref())
+            .map(|tr| tr.id().clone())
+            .unwrap_or_else(|| TranslationId::from(""));
+        Revision {
+            id: None,
+            record,
+            actor: actor.clone(),
+            at: SystemTime::now(),
+            before,
+            after,
+        }
+    }
+
+    /// Sets the ID, e.g. when stored or read back from a repository
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = Some(RevisionId::new(id));
+        self
+    }
+
+    /// Sets the time of the change, e.g. when read back from a repository
+    pub fn with_at(mut self, at: SystemTime) -> Self {
+        self.at = at;
+        self
+    }
+
+    pub fn id(&self) -> Option<&RevisionId> {
+        self.id.as_ref()
+    }
+
+    /// ID of the changed TranslationRecord
+    pub fn record(&self) -> &TranslationId {
+        &self.record
+    }
+
+    pub fn actor(&self) -> &UserId {
+        &self.actor
+    }
+
+    pub fn at(&self) -> SystemTime {
+        self.at
+    }
+
+    pub fn before(&self) -> Option<&TranslationRecord> {
+        self.before.as_ref()
+    }
+
+    pub fn after(&self) -> Option<&TranslationRecord> {
+        self.after.as_ref()
+    }
+
+    pub fn change(&self) -> Change {
+        match (&self.before, &self.after) {
+            (None, _) => Change::Created,
+            (Some(_), None) => Change::Deleted,
+            (Some(before), Some(_)) if before.deleted_at().is_some() => Change::Restored,
+            (Some(_), Some(_)) => Change::Updated,
+        }
+    }
+
+    /// Owner of the changed TranslationRecord
+    fn owner(&self) -> Option<&UserId> {
+        self.after
+            .as_ref()
+            .or(self.before.as_ref())
+            .map(TranslationRecord::owner)
+    }
+}
+
+#[derive(Debug, PartialEq, Error)]
+pub enum RevisionError {
+    #[error("Translation not found")]
+    NotFound,
+    #[error("Revision not found")]
+    UnknownRevision,
+    #[error("The revision deleted the record, restore it from the trash instead")]
+    Deletion,
+    #[error("Read-only access")]
+    ReadOnly,
+    #[error("Read Error: {0}")]
+    Read(#[from] RepoReadError),
+    #[error("Update Error")]
+    Update(#[from] RepoUpdateError),
+}
+
+/// Appends the revision of a change done by the user of `access`
+///
+/// The change is done already, so a revision that cannot be stored is logged instead of
+/// failing it.
+pub(crate) async fn record_revision(
+    repository: &impl RevisionRepository,
+    access: &Access,
+    before: Option<TranslationRecord>,
+    after: Option<TranslationRecord>,
+) {
+    let revision = Revision::new(access.user(), before, after);
+    if let Err(e) = repository.add_revision(&revision).await {
+        tracing::error!(record = ?revision.record().value(), "Cannot record revision: {e}");
+    }
+}
+
+/// Revisions of a readable record, oldest first, also of records in the trash
+#[instrument(skip_all, fields(access = %access, id = ?id.value()), err(level = "info"))]
+pub async fn translation_history(
+    repository: &impl RevisionRepository,
+    access: &Access,
+    id: &TranslationId,
+) -> Result<Vec<Revision>, RevisionError> {
+    let revisions = repository.revisions_of(id).await?;
+
+    let readable = revisions
+        .last()
+        .and_then(Revision::owner)
+        .is_some_and(|owner| access.readable().contains(owner));
+    if !readable {
+        return Err(RevisionError::NotFound);
+    }
+    Ok(revisions)
+}
+
+/// Sets a record back to its state after `revision`, recorded as a revision of its own
+///
+/// # Errors
+/// Returns `RevisionError::Deletion` if `revision` moved the record to the trash and
+/// `RevisionError::NotFound` if the record is in the trash, restore it first
+#[instrument(skip_all, fields(access = %access, id = ?id.value(), revision = %revision.value()), err(level = "info"))]
+pub async fn revert_translation(
+    repository: &(impl TranslationRepository + RevisionRepository),
+    access: &Access,
+    id: &TranslationId,
+    revision: &RevisionId,
+) -> Result<TranslationRecord, RevisionError> {
+    let current = repository
+        .read_by_ids(access.readable(), std::slice::from_ref(id))
+        .await?
+        .pop()
+        .ok_or(RevisionError::NotFound)?;
+    if !access.can_write(current.owner()) {
+        return Err(RevisionError::ReadOnly);
+    }
+
+    let revision = repository
+        .revisions_of(id)
+        .await?
+        .into_iter()
+        .find(|r| r.id() == Some(revision))
+        .ok_or(RevisionError::UnknownRevision)?;
+    let target = revision.after().ok_or(RevisionError::Deletion)?;
+
+    let reverted = repository.update(target).await?;
+    record_revision(repository, access, Some(current), Some(reverted.clone())).await;
+    Ok(reverted)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::domain::delete_translation::delete_translation;
+    use crate::domain::update_translation::update_translation;
+    use crate::test_utils::{utils::shared::*, voci_repo_double::repo_double::VociRepoDouble};
+
+    async fn update(repo: &VociRepoDouble, word: &str, translation: &str) -> TranslationRecord {
+        update_translation(
+            repo,
+            &access(),
+            word,
+            &WORD_LANG,
+            None,
+            &[translation],
+            &TRANSLATION_LANG,
+        )
+        .await
+        .unwrap()
+    }
+
+    #[actix_rt::test]
+    async fn changes_recorded_in_history() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        let chat = create_word(&repo, &access(), "chat").await;
+        let updated = update(&repo, "chat", "Katze").await;
+        delete_translation(&repo, &access(), "chat", &WORD_LANG, None)
+            .await
+            .unwrap();
+
+        let history = translation_history(&repo, &access(), chat.id())
+            .await
+            .unwrap();
+
+        let changes: Vec<Change> = history.iter().map(Revision::change).collect();
+        assert_eq!(
+            changes,
+            vec![Change::Created, Change::Updated, Change::Deleted]
+        );
+        assert!(history.iter().all(|r| r.actor() == &owner()));
+        assert_eq!(history[1].before(), Some(&chat));
+        assert_eq!(history[1].after(), Some(&updated));
+    }
+
+    #[actix_rt::test]
+    async fn revert_to_creation_drops_later_translations() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        let chat = create_word(&repo, &access(), "chat").await;
+        update(&repo, "chat", "Katze").await;
+        let created = translation_history(&repo, &access(), chat.id())
+            .await
+            .unwrap()[0]
+            .clone();
+
+        let reverted = revert_translation(&repo, &access(), chat.id(), created.id().unwrap()).await;
+        let history = translation_history(&repo, &access(), chat.id())
+            .await
+            .unwrap();
+
+        assert_eq!(reverted, Ok(chat.clone()));
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[2].after(), Some(&chat));
+    }
+
+    #[actix_rt::test]
+    async fn history_of_other_owner_or_revert_deletion_err() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        let chat = create_word(&repo, &access(), "chat").await;
+        let other = Access::own(&UserId::new(OTHER_OWNER));
+
+        let foreign = translation_history(&repo, &other, chat.id()).await;
+        let unknown =
+            revert_translation(&repo, &access(), chat.id(), &RevisionId::new("gone")).await;
+        let deletion = Revision::new(&owner(), Some(chat.clone()), None);
+        let deletion = repo.add_revision(&deletion).await.unwrap();
+        let reverted =
+            revert_translation(&repo, &access(), chat.id(), deletion.id().unwrap()).await;
+
+        assert_eq!(foreign, Err(RevisionError::NotFound));
+        assert_eq!(unknown, Err(RevisionError::UnknownRevision));
+        assert_eq!(reverted, Err(RevisionError::Deletion));
+    }
+}
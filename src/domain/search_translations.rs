@@ -0,0 +1,174 @@
+use thiserror::Error;
+use tracing::instrument;
+
+use crate::domain::deck::Access;
+use crate::domain::ports::{RepoReadError, TranslationRepository};
+use crate::domain::text;
+use crate::domain::voci::{Lang, TranslationRecord};
+
+/// Most records returned by a search
+pub const MAX_RESULTS: usize = 50;
+
+#[derive(Debug, PartialEq, Error)]
+pub enum SearchError {
+    #[error("Search query is empty")]
+    EmptyQuery,
+    #[error("Unknown")]
+    Unknown,
+}
+
+/// How a record matches a query, better matches first
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Match {
+    Exact,
+    Prefix,
+    Substring,
+}
+
+/// Best match of `key` against the word and translations of `tr`, `None` if nothing matches
+pub fn best_match(tr: &TranslationRecord, key: &str, lang: Option<&Lang>) -> Option<Match> {
+    let (_, word, word_lang, translations, translation_lang) = tr.flat();
+    let mut candidates = Vec::new();
+    if lang.is_none_or(|l| l == word_lang) {
+        candidates.push(word);
+    }
+    if lang.is_none_or(|l| l == translation_lang) {
+        candidates.extend(translations);
+    }
+
+    candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let candidate = text::search_key(candidate);
+            if candidate == key {
+                Some(Match::Exact)
+            } else if candidate.starts_with(key) {
+                Some(Match::Prefix)
+            } else if candidate.contains(key) {
+                Some(Match::Substring)
+            } else {
+                None
+            }
+        })
+        .min()
+}
+
+/// Finds the readable records whose word or translations contain `query`
+///
+/// Letter case and accents are ignored. Exact matches come first, then prefix, then substring
+/// matches, each ordered by word. At most `MAX_RESULTS` records are returned. Substring matches
+/// are only searched for, the slow way, if there are fewer prefix matches than that.
+#[instrument(skip_all, fields(access = %access, query = %query), err(level = "info"))]
+pub async fn search_translations(
+    repository: &impl TranslationRepository,
+    access: &Access,
+    query: &str,
+    lang: Option<&Lang>,
+) -> Result<Vec<TranslationRecord>, SearchError> {
+    let key = text::search_key(query);
+    if key.is_empty() {
+        return Err(SearchError::EmptyQuery);
+    }
+
+    let key = key.as_str();
+    let search = |anywhere| async move {
+        repository
+            .search(access.readable(), key, lang, anywhere)
+            .await
+            .map_err(|e| match e {
+                RepoReadError::NotFound | RepoReadError::Unknown => SearchError::Unknown,
+            })
+    };
+    let rank = |tr: TranslationRecord| {
+        let found = best_match(&tr, key, lang)?;
+        Some((found, tr.word().search_key(), tr))
+    };
+
+    let mut ranked: Vec<(Match, String, TranslationRecord)> =
+        search(false).await?.into_iter().filter_map(rank).collect();
+    if ranked.len() < MAX_RESULTS {
+        // Records matching by prefix were found above already
+        ranked.extend(
+            search(true)
+                .await?
+                .into_iter()
+                .filter_map(rank)
+                .filter(|(found, _, _)| *found == Match::Substring),
+        );
+    }
+    ranked.sort_by(|a, b| (a.0, &a.1).cmp(&(b.0, &b.1)));
+
+    Ok(ranked
+        .into_iter()
+        .take(MAX_RESULTS)
+        .map(|(_, _, tr)| tr)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::domain::user::UserId;
+    use crate::test_utils::{utils::shared::*, voci_repo_double::repo_double::VociRepoDouble};
+
+    fn record(word: &str, translations: &[&str]) -> TranslationRecord {
+        TranslationRecord::new(
+            None,
+            &UserId::new(OWNER),
+            word,
+            &Lang::fr,
+            translations,
+            &Lang::de,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn best_match_exact_prefix_substring() {
+        let eleve = record("élève", &["Schüler"]);
+
+        assert_eq!(best_match(&eleve, "eleve", None), Some(Match::Exact));
+        assert_eq!(best_match(&eleve, "schu", None), Some(Match::Prefix));
+        assert_eq!(best_match(&eleve, "lev", None), Some(Match::Substring));
+        assert_eq!(best_match(&eleve, "schu", Some(&Lang::fr)), None);
+        assert_eq!(best_match(&eleve, "chat", None), None);
+    }
+
+    #[actix_rt::test]
+    async fn search_by_word_or_translation_found() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+
+        let by_word = search_translations(&repo, &access(), "CHI", None).await;
+        let by_translation = search_translations(&repo, &access(), "KÖT", Some(&Lang::de)).await;
+        let wrong_lang = search_translations(&repo, &access(), "hund", Some(&Lang::fr)).await;
+
+        assert_eq!(by_word.unwrap(), vec![stub_translation_record(false)]);
+        assert_eq!(
+            by_translation.unwrap(),
+            vec![stub_translation_record(false)]
+        );
+        assert_eq!(wrong_lang.unwrap(), vec![]);
+    }
+
+    #[actix_rt::test]
+    async fn search_substring_matches_after_prefix_matches() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        let machin = create_word(&repo, &access(), "machin").await;
+
+        let found = search_translations(&repo, &access(), "chi", None).await;
+        let inner = search_translations(&repo, &access(), "und", None).await;
+
+        assert_eq!(found.unwrap(), vec![stub_translation_record(false), machin]);
+        assert_eq!(inner.unwrap(), vec![stub_translation_record(false)]);
+    }
+
+    #[actix_rt::test]
+    async fn search_blank_query_err() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+
+        let result = search_translations(&repo, &access(), " \u{301} ", None).await;
+
+        assert_eq!(result.unwrap_err(), SearchError::EmptyQuery);
+    }
+}
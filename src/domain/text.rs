@@ -0,0 +1,98 @@
+use unicode_normalization::UnicodeNormalization;
+use unicode_normalization::char::is_combining_mark;
+
+/// Longest word or translation accepted, in characters of the canonical form
+pub const MAX_LEN: usize = 100;
+
+/// Canonical form of a word as stored: NFC, trimmed and inner whitespace collapsed to one space
+pub fn canonical(s: &str) -> String {
+    s.nfc()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Key words are looked up by, ignoring letter case and accents
+///
+/// "Élève", "eleve" and a decomposed "e\u{301}le\u{300}ve" all share the key "eleve".
+pub fn search_key(s: &str) -> String {
+    canonical(s)
+        .nfd()
+        .filter(|c| !is_combining_mark(*c))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Levenshtein distance of `a` and `b`, counted in characters
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+/// First character not allowed in words, control characters and invisible formatting such as
+/// zero-width spaces, joiners, bidi controls and byte order marks
+pub fn illegal_char(s: &str) -> Option<char> {
+    s.chars().find(|c| {
+        c.is_control()
+            || matches!(
+                c,
+                '\u{00AD}'
+                    | '\u{180E}'
+                    | '\u{200B}'..='\u{200F}'
+                    | '\u{202A}'..='\u{202E}'
+                    | '\u{2060}'..='\u{2064}'
+                    | '\u{2066}'..='\u{2069}'
+                    | '\u{FEFF}'
+            )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical_composed_trimmed_and_collapsed() {
+        assert_eq!(canonical("  pomme \t de\n terre "), "pomme de terre");
+        assert_eq!(canonical("e\u{301}le\u{300}ve"), "élève");
+        assert_eq!(canonical(" \u{3000} "), "");
+    }
+
+    #[test]
+    fn illegal_char_control_and_zero_width_found() {
+        assert_eq!(illegal_char("chien"), None);
+        assert_eq!(illegal_char("pomme de terre"), None);
+        assert_eq!(illegal_char("chi\u{200B}en"), Some('\u{200B}'));
+        assert_eq!(illegal_char("\u{FEFF}chien"), Some('\u{FEFF}'));
+        assert_eq!(illegal_char("chien\u{7}"), Some('\u{7}'));
+    }
+
+    #[test]
+    fn edit_distance_counts_characters() {
+        assert_eq!(edit_distance("chien", "chien"), 0);
+        assert_eq!(edit_distance("chein", "chien"), 2);
+        assert_eq!(edit_distance("chie", "chien"), 1);
+        assert_eq!(edit_distance("eleve", "élève"), 2);
+        assert_eq!(edit_distance("", "chat"), 4);
+    }
+
+    #[test]
+    fn search_key_ignores_case_and_accents() {
+        assert_eq!(search_key("Élève "), "eleve");
+        assert_eq!(search_key("e\u{301}le\u{300}ve"), "eleve");
+        assert_eq!(search_key("Straße"), "straße");
+        assert_eq!(search_key("Chien"), search_key("chien "));
+    }
+}
@@ -0,0 +1,160 @@
+use std::time::{Duration, SystemTime};
+use thiserror::Error;
+use tracing::instrument;
+
+use crate::domain::deck::Access;
+use crate::domain::ports::{
+    LinkRepository, RepoDeleteError, RepoReadError, RepoUpdateError, RevisionRepository,
+    TranslationRepository,
+};
+use crate::domain::revision::record_revision;
+use crate::domain::voci::{TranslationId, TranslationRecord};
+
+#[derive(Debug, PartialEq, Error)]
+pub enum TrashError {
+    #[error("Translation not in the trash")]
+    NotFound,
+    #[error("Duplicate")]
+    Duplicate,
+    #[error("Read-only access")]
+    ReadOnly,
+    #[error("Read Error: {0}")]
+    Read(#[from] RepoReadError),
+    #[error("Update Error")]
+    Update(#[from] RepoUpdateError),
+    #[error("Delete Error")]
+    Delete(#[from] RepoDeleteError),
+}
+
+/// Records deleted from the writable vocabulary, most recently deleted first
+#[instrument(skip_all, fields(access = %access), err(level = "info"))]
+pub async fn list_trash(
+    repository: &impl TranslationRepository,
+    access: &Access,
+) -> Result<Vec<TranslationRecord>, TrashError> {
+    let owner = access.writable().ok_or(TrashError::ReadOnly)?;
+
+    let mut trashed = repository.trash(std::slice::from_ref(owner)).await?;
+    trashed.sort_by_key(|tr| std::cmp::Reverse(tr.deleted_at()));
+    Ok(trashed)
+}
+
+/// Takes a record of the writable vocabulary out of the trash
+///
+/// # Errors
+/// Returns `TrashError::Duplicate` if the word was created again in the same sense meanwhile
+#[instrument(skip_all, fields(access = %access, id = ?id.value()), err(level = "info"))]
+pub async fn restore_translation(
+    repository: &(impl TranslationRepository + RevisionRepository),
+    access: &Access,
+    id: &TranslationId,
+) -> Result<TranslationRecord, TrashError> {
+    let owner = access.writable().ok_or(TrashError::ReadOnly)?;
+    let owners = std::slice::from_ref(owner);
+
+    let trashed = repository
+        .trash(owners)
+        .await?
+        .into_iter()
+        .find(|tr| tr.id() == id)
+        .ok_or(TrashError::NotFound)?;
+    let taken = repository
+        .read_by_word(owners, trashed.word())
+        .await
+        .is_ok_and(|senses| senses.iter().any(|s| s.is_sense(trashed.sense())));
+    if taken {
+        return Err(TrashError::Duplicate);
+    }
+
+    let restored = repository.restore(owner, id).await?;
+    record_revision(repository, access, Some(trashed), Some(restored.clone())).await;
+    Ok(restored)
+}
+
+/// Deletes the records in the trash for longer than `retention` for good, along with their links
+///
+/// Returns the number of records deleted.
+#[instrument(skip(repository), err)]
+pub async fn purge_trash(
+    repository: &(impl TranslationRepository + LinkRepository),
+    retention: Duration,
+) -> Result<usize, TrashError> {
+    let deleted_before = SystemTime::now()
+        .checked_sub(retention)
+        .unwrap_or(SystemTime::UNIX_EPOCH);
+
+    let purged = repository.purge(deleted_before).await?;
+    for id in &purged {
+        repository.remove_links(id).await?;
+    }
+    Ok(purged.len())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::domain::delete_translation::delete_translation;
+    use crate::domain::link::{Relation, link_translations};
+    use crate::domain::read_translation::read_translation;
+    use crate::test_utils::{utils::shared::*, voci_repo_double::repo_double::VociRepoDouble};
+
+    async fn delete(repo: &VociRepoDouble, word: &str) {
+        delete_translation(repo, &access(), word, &WORD_LANG, None)
+            .await
+            .unwrap();
+    }
+
+    #[actix_rt::test]
+    async fn deleted_record_hidden_until_restored() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        let chat = create_word(&repo, &access(), "chat").await;
+
+        delete(&repo, "chat").await;
+        let trash = list_trash(&repo, &access()).await.unwrap();
+        let hidden = read_translation(&repo, &access(), "chat", &WORD_LANG, None).await;
+        let restored = restore_translation(&repo, &access(), chat.id()).await;
+        let read = read_translation(&repo, &access(), "chat", &WORD_LANG, None).await;
+
+        assert_eq!(trash.len(), 1);
+        assert_eq!(trash[0].id(), chat.id());
+        assert!(trash[0].deleted_at().is_some());
+        assert!(hidden.is_err());
+        assert_eq!(restored, Ok(chat.clone()));
+        assert_eq!(read, Ok(vec![chat]));
+        assert_eq!(list_trash(&repo, &access()).await, Ok(vec![]));
+    }
+
+    #[actix_rt::test]
+    async fn restore_recreated_word_duplicate() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        let chat = create_word(&repo, &access(), "chat").await;
+        delete(&repo, "chat").await;
+        create_word(&repo, &access(), "chat").await;
+
+        let restored = restore_translation(&repo, &access(), chat.id()).await;
+        let unknown = restore_translation(&repo, &access(), &TranslationId::from("gone")).await;
+
+        assert_eq!(restored, Err(TrashError::Duplicate));
+        assert_eq!(unknown, Err(TrashError::NotFound));
+    }
+
+    #[actix_rt::test]
+    async fn purge_deletes_expired_records_with_links() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        let chat = create_word(&repo, &access(), "chat").await;
+        let chatte = create_word(&repo, &access(), "chatte").await;
+        link_translations(&repo, &access(), chat.id(), chatte.id(), Relation::Family)
+            .await
+            .unwrap();
+        delete(&repo, "chatte").await;
+
+        let kept = purge_trash(&repo, Duration::from_secs(3600)).await;
+        let purged = purge_trash(&repo, Duration::ZERO).await;
+
+        assert_eq!(kept, Ok(0));
+        assert_eq!(purged, Ok(1));
+        assert_eq!(list_trash(&repo, &access()).await, Ok(vec![]));
+        assert_eq!(repo.links_of(&[chat.id().clone()]).await, Ok(vec![]));
+    }
+}
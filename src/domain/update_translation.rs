@@ -1,8 +1,13 @@
 use std::ops::Deref;
 use thiserror::Error;
+use tracing::instrument;
 
-use crate::domain::ports::{RepoReadError, RepoUpdateError, TranslationRepository};
-use crate::domain::voci::{Lang, TranslationRecord, TranslationRecordError, Word};
+use crate::domain::deck::Access;
+use crate::domain::ports::{
+    RepoReadError, RepoUpdateError, RevisionRepository, TranslationRepository,
+};
+use crate::domain::revision::record_revision;
+use crate::domain::voci::{Lang, TranslationRecord, TranslationRecordError, Word, select_sense};
 
 #[derive(Debug, PartialEq, Error)]
 pub enum UpdateError {
@@ -12,6 +17,8 @@ pub enum UpdateError {
     Read(#[from] RepoReadError),
     #[error("Update Error:")]
     Update(#[from] RepoUpdateError),
+    #[error("Read-only access")]
+    ReadOnly,
 }
 
 /// Updates a translation record for a given word and language.
@@ -19,6 +26,7 @@ pub enum UpdateError {
 /// # Arguments
 /// * `word` - The word to update the translation for
 /// * `lang` - The language of the word
+/// * `sense` - The sense to update, needed if the word has several
 ///
 /// # Returns
 /// * `Result<TranslationRecord, UpdateError>` - The updated translation record if successful,
@@ -28,10 +36,16 @@ pub enum UpdateError {
 /// Returns `UpdateError::WordError` if:
 /// * The word is empty or invalid
 /// * The language specification is invalid
+/// * The word has several senses and `sense` names none of them
+///
+/// Returns `UpdateError::ReadOnly` if the record belongs to a deck the user only subscribed to
+#[instrument(skip_all, fields(access = %access, word = %word, lang = %lang), err(level = "info"))]
 pub async fn update_translation<S>(
-    repository: &impl TranslationRepository,
+    repository: &(impl TranslationRepository + RevisionRepository),
+    access: &Access,
     word: &str,
     lang: &Lang,
+    sense: Option<&str>,
     extra_translations: &[S],
     extra_translation_lang: &Lang,
 ) -> Result<TranslationRecord, UpdateError>
@@ -40,7 +54,12 @@ where
 {
     let word = Word::new(word, lang)?;
 
-    let mut tr_to_be_updated = repository.read_by_word(&word).await?;
+    let senses = repository.read_by_word(access.readable(), &word).await?;
+    let mut tr_to_be_updated = select_sense(senses, sense)?;
+    if !access.can_write(tr_to_be_updated.owner()) {
+        return Err(UpdateError::ReadOnly);
+    }
+    let before = tr_to_be_updated.clone();
 
     tr_to_be_updated.update(
         extra_translations.iter().map(|t| t.to_string()).collect(),
@@ -48,6 +67,7 @@ where
     )?;
 
     let updated_tr = repository.update(&tr_to_be_updated).await?;
+    record_revision(repository, access, Some(before), Some(updated_tr.clone())).await;
 
     Ok(updated_tr)
 }
@@ -56,6 +76,8 @@ where
 mod tests {
 
     use super::*;
+    use crate::domain::deck::{create_deck, resolve_access, subscribe};
+    use crate::domain::user::UserId;
     use crate::test_utils::{utils::shared::*, voci_repo_double::repo_double::VociRepoDouble};
 
     #[actix_rt::test]
@@ -65,8 +87,10 @@ mod tests {
         let empty_translations: [&str; 0] = [];
         let updated_tr = update_translation(
             &repo,
+            &access(),
             WORD,
             &WORD_LANG,
+            None,
             &empty_translations,
             &TRANSLATION_LANG,
         )
@@ -89,8 +113,10 @@ mod tests {
 
         let updated_tr = update_translation(
             &repo,
+            &access(),
             WORD,
             &WORD_LANG,
+            None,
             &ADDITONAL_TRANSLATIONS,
             &TRANSLATION_LANG,
         )
@@ -100,4 +126,77 @@ mod tests {
         let (_, _, _, actual_translations, _) = updated_translation.flat();
         assert_on_translations(actual_translations, &expected);
     }
+
+    #[actix_rt::test]
+    async fn update_homograph_named_sense_only() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        create_homographs(&repo).await;
+        let access = access();
+        let update = |sense| {
+            update_translation(
+                &repo,
+                &access,
+                "avocat",
+                &WORD_LANG,
+                sense,
+                &["Advokat"],
+                &TRANSLATION_LANG,
+            )
+        };
+
+        let ambiguous = update(None).await;
+        let lawyer = update(Some("lawyer")).await.unwrap();
+
+        assert_eq!(
+            ambiguous,
+            Err(UpdateError::Word(TranslationRecordError::AmbiguousSense))
+        );
+        assert_eq!(lawyer.sense(), Some("lawyer"));
+        assert_eq!(lawyer.flat().3, &["Anwalt", "Advokat"]);
+    }
+
+    #[actix_rt::test]
+    async fn update_record_of_subscribed_deck_read_only() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        let deck = create_deck(&repo, &owner(), "Animals").await.unwrap();
+        let student = UserId::new(OTHER_OWNER);
+        subscribe(&repo, &student, deck.id()).await.unwrap();
+        let student = resolve_access(&repo, &student, None).await.unwrap();
+
+        let updated_tr = update_translation(
+            &repo,
+            &student,
+            WORD,
+            &WORD_LANG,
+            None,
+            &ADDITONAL_TRANSLATIONS,
+            &TRANSLATION_LANG,
+        )
+        .await;
+
+        assert_eq!(updated_tr, Err(UpdateError::ReadOnly));
+    }
+
+    #[actix_rt::test]
+    async fn update_record_of_deck_by_editor() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        let deck = create_deck(&repo, &owner(), "Animals").await.unwrap();
+        let editor = resolve_access(&repo, &owner(), Some(deck.id()))
+            .await
+            .unwrap();
+
+        let updated_tr = update_translation(
+            &repo,
+            &editor,
+            WORD,
+            &WORD_LANG,
+            None,
+            &ADDITONAL_TRANSLATIONS,
+            &TRANSLATION_LANG,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(updated_tr.owner(), &deck.vocabulary());
+    }
 }
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Owner of records created without a logged in user, and of records predating user accounts
+const ANONYMOUS: &str = "anonymous";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct UserId(String);
+
+impl UserId {
+    pub fn new(id: &str) -> Self {
+        UserId(id.to_string())
+    }
+
+    pub fn anonymous() -> Self {
+        UserId(ANONYMOUS.to_string())
+    }
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for UserId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A user account, the password is only kept as argon2 hash in PHC string format
+#[derive(Debug, Clone, PartialEq)]
+pub struct User {
+    id: UserId,
+    name: String,
+    password_hash: String,
+}
+
+impl User {
+    pub fn new(id: &str, name: &str, password_hash: &str) -> Self {
+        User {
+            id: UserId::new(id),
+            name: name.to_string(),
+            password_hash: password_hash.to_string(),
+        }
+    }
+
+    pub fn id(&self) -> &UserId {
+        &self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn password_hash(&self) -> &str {
+        &self.password_hash
+    }
+}
@@ -1,17 +1,105 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
+use std::fmt;
 use std::ops::Deref;
+use std::str::FromStr;
+use std::time::SystemTime;
 use thiserror::Error;
+use utoipa::ToSchema;
+
+use crate::domain::conjugation::Conjugation;
+use crate::domain::text;
+use crate::domain::user::UserId;
 
 /// Represents available languages in the system
 /// Languages codes according to https://de.wikipedia.org/wiki/Liste_der_ISO-639-2-Codes
 #[allow(non_camel_case_types)]
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, ToSchema)]
 pub enum Lang {
     fr, // french
     de, // german
 }
 
+impl FromStr for Lang {
+    type Err = TranslationRecordError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fr" => Ok(Lang::fr),
+            "de" => Ok(Lang::de),
+            _ => Err(TranslationRecordError::UnsupportedLang(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Lang {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Lang::fr => write!(f, "fr"),
+            Lang::de => write!(f, "de"),
+        }
+    }
+}
+
+impl Lang {
+    /// Leading articles of nouns and the gender they mark, the elided `l'` marks none
+    fn articles(&self) -> &'static [(&'static str, Option<Gender>)] {
+        match self {
+            Lang::fr => &[
+                ("le ", Some(Gender::Masculine)),
+                ("la ", Some(Gender::Feminine)),
+                ("les ", Some(Gender::Plural)),
+                ("l'", None),
+                ("l\u{2019}", None),
+            ],
+            Lang::de => &[
+                ("der ", Some(Gender::Masculine)),
+                ("die ", Some(Gender::Feminine)),
+                ("das ", Some(Gender::Neuter)),
+            ],
+        }
+    }
+
+    /// Splits a leading article off `s`, ignoring letter case
+    ///
+    /// Returns the article as listed in `articles`, with its gender, and the rest. Nothing is
+    /// split off if the rest would be empty.
+    pub fn split_article<'a>(&self, s: &'a str) -> (Option<Article>, &'a str) {
+        for (article, gender) in self.articles() {
+            let Some(prefix) = s.get(..article.len()) else {
+                continue;
+            };
+            let rest = s[article.len()..].trim_start();
+            if prefix.to_lowercase() == *article && !rest.is_empty() {
+                let article = Article {
+                    text: article.trim_end(),
+                    gender: *gender,
+                };
+                return (Some(article), rest);
+            }
+        }
+        (None, s)
+    }
+}
+
+/// Grammatical gender marked by an article, `Plural` for articles of plural nouns
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Gender {
+    Masculine,
+    Feminine,
+    Neuter,
+    Plural,
+}
+
+/// A leading article as recognized by `Lang::split_article`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Article {
+    /// Lower case article without trailing space, e.g. `der` or `l'`
+    pub text: &'static str,
+    pub gender: Option<Gender>,
+}
+
 #[derive(Debug, PartialEq, Error)]
 pub enum TranslationRecordError {
     #[error("Word is Empty")]
@@ -24,6 +112,18 @@ pub enum TranslationRecordError {
     TranslationLanguageMismatch,
     #[error("Update with same items")]
     UpdateWithSameItems,
+    #[error("Unsupported language: {0}")]
+    UnsupportedLang(String),
+    #[error("Word or translation consists of whitespace only")]
+    Blank,
+    #[error("Word or translation longer than {} characters", text::MAX_LEN)]
+    TooLong,
+    #[error("Illegal character U+{:04X} in word or translation", *.0 as u32)]
+    IllegalCharacter(char),
+    #[error("Word has several senses, name one of them")]
+    AmbiguousSense,
+    #[error("Word has no sense {0}")]
+    UnknownSense(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -64,21 +164,34 @@ impl From<Option<&str>> for TranslationId {
 pub struct Word {
     word: String,
     lang: Lang,
+    gender: Option<Gender>,
 }
 
 impl Word {
+    /// Takes the canonical form of `word`, see `text::canonical`
+    ///
+    /// A leading article of `lang` is stripped into the gender, "le chien" becomes "chien".
     pub fn new(word: &str, lang: &Lang) -> Result<Self, TranslationRecordError> {
-        if word.is_empty() {
-            return Err(TranslationRecordError::EmptyWord);
-        }
+        let word = checked(word, TranslationRecordError::EmptyWord)?;
+        let (article, bare) = lang.split_article(&word);
         Ok(Word {
-            word: word.to_string(),
+            word: bare.to_string(),
             lang: lang.clone(),
+            gender: article.and_then(|a| a.gender),
         })
     }
     pub fn value(&self) -> (&String, &Lang) {
         (&self.word, &self.lang)
     }
+
+    pub fn gender(&self) -> Option<Gender> {
+        self.gender
+    }
+
+    /// Case and accent folded form the word is looked up by
+    pub fn search_key(&self) -> String {
+        text::search_key(&self.word)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -96,12 +209,13 @@ impl Translations {
             return Err(TranslationRecordError::EmptyTranslation);
         }
 
-        if words.iter().any(|s| s.is_empty()) {
-            return Err(TranslationRecordError::EmptyWordInTranslation);
-        }
+        let words = words
+            .iter()
+            .map(|s| checked(s, TranslationRecordError::EmptyWordInTranslation))
+            .collect::<Result<Vec<_>, _>>()?;
 
         Ok(Translations {
-            words: words.iter().map(|s| s.to_string()).collect(),
+            words,
             lang: lang.clone(),
         })
     }
@@ -117,13 +231,21 @@ impl Translations {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TranslationRecord {
     id: TranslationId,
+    owner: UserId,
     word: Word,
+    /// Label telling apart the records of homographs, e.g. "fruit" for one "avocat"
+    sense: Option<String>,
     translations: Translations,
+    /// Conjugation table of a French verb
+    conjugation: Option<Conjugation>,
+    /// When the record was moved to the trash, if it was
+    deleted_at: Option<SystemTime>,
 }
 
 impl TranslationRecord {
     pub fn new<S>(
         id: Option<&str>,
+        owner: &UserId,
         word: &str,
         word_lang: &Lang,
         translations: &[S],
@@ -138,8 +260,12 @@ impl TranslationRecord {
 
         Ok(TranslationRecord {
             id,
+            owner: owner.clone(),
             word,
+            sense: None,
             translations,
+            conjugation: None,
+            deleted_at: None,
         })
     }
 
@@ -147,10 +273,60 @@ impl TranslationRecord {
         &self.id
     }
 
+    pub fn owner(&self) -> &UserId {
+        &self.owner
+    }
+
     pub fn word(&self) -> &Word {
         &self.word
     }
 
+    pub fn sense(&self) -> Option<&str> {
+        self.sense.as_deref()
+    }
+
+    /// Labels the sense of the word, the empty label is none
+    pub fn with_sense(mut self, sense: Option<&str>) -> Result<Self, TranslationRecordError> {
+        self.sense = sense
+            .filter(|s| !s.is_empty())
+            .map(|s| checked(s, TranslationRecordError::Blank))
+            .transpose()?;
+        Ok(self)
+    }
+
+    /// Whether the record is of `sense`, sense labels are compared by their search keys
+    pub fn is_sense(&self, sense: Option<&str>) -> bool {
+        self.sense.as_deref().map(text::search_key) == sense.map(text::search_key)
+    }
+
+    pub fn conjugation(&self) -> Option<&Conjugation> {
+        self.conjugation.as_ref()
+    }
+
+    /// Attaches the conjugation table of a verb, replacing any former one
+    pub fn with_conjugation(mut self, conjugation: Option<Conjugation>) -> Self {
+        self.conjugation = conjugation;
+        self
+    }
+
+    pub fn deleted_at(&self) -> Option<SystemTime> {
+        self.deleted_at
+    }
+
+    /// Sets when the record was moved to the trash, e.g. when read back from a repository
+    pub fn with_deleted_at(mut self, deleted_at: Option<SystemTime>) -> Self {
+        self.deleted_at = deleted_at;
+        self
+    }
+
+    /// Sets the gender of the word, if known, e.g. when read back from a repository
+    pub fn with_gender(mut self, gender: Option<Gender>) -> Self {
+        if gender.is_some() {
+            self.word.gender = gender;
+        }
+        self
+    }
+
     pub fn update(
         &mut self,
         translations: Vec<String>,
@@ -160,6 +336,12 @@ impl TranslationRecord {
             return Err(TranslationRecordError::TranslationLanguageMismatch);
         }
 
+        let translations = translations
+            .iter()
+            .filter(|t| !t.is_empty())
+            .map(|t| checked(t, TranslationRecordError::EmptyWordInTranslation))
+            .collect::<Result<Vec<_>, _>>()?;
+
         if vectors_are_equal(&self.translations.words, &translations) {
             return Err(TranslationRecordError::UpdateWithSameItems);
         }
@@ -184,6 +366,45 @@ impl TranslationRecord {
     }
 }
 
+/// Picks the record of `sense` among the senses of a word
+///
+/// Without `sense`, a word of a single sense yields that one, otherwise the unlabeled sense.
+pub fn select_sense(
+    senses: Vec<TranslationRecord>,
+    sense: Option<&str>,
+) -> Result<TranslationRecord, TranslationRecordError> {
+    if let ([_], None) = (senses.as_slice(), sense) {
+        return Ok(senses.into_iter().next().unwrap());
+    }
+
+    match (senses.into_iter().find(|tr| tr.is_sense(sense)), sense) {
+        (Some(tr), _) => Ok(tr),
+        (None, Some(sense)) => Err(TranslationRecordError::UnknownSense(sense.to_string())),
+        (None, None) => Err(TranslationRecordError::AmbiguousSense),
+    }
+}
+
+/// Canonical form of a word or translation, `empty` is the error for the empty string
+pub(crate) fn checked(
+    raw: &str,
+    empty: TranslationRecordError,
+) -> Result<String, TranslationRecordError> {
+    if raw.is_empty() {
+        return Err(empty);
+    }
+    let canonical = text::canonical(raw);
+    if canonical.is_empty() {
+        return Err(TranslationRecordError::Blank);
+    }
+    if let Some(c) = text::illegal_char(&canonical) {
+        return Err(TranslationRecordError::IllegalCharacter(c));
+    }
+    if canonical.chars().count() > text::MAX_LEN {
+        return Err(TranslationRecordError::TooLong);
+    }
+    Ok(canonical)
+}
+
 fn vectors_are_equal<T: Ord + Clone>(vec1: &[T], vec2: &[T]) -> bool {
     if vec1.len() != vec2.len() {
         return false;
@@ -206,6 +427,20 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn lang_from_str_known_code_parsed() {
+        assert_eq!("fr".parse::<Lang>().unwrap(), Lang::fr);
+        assert_eq!("de".parse::<Lang>().unwrap(), Lang::de);
+    }
+
+    #[test]
+    fn lang_from_str_unknown_code_err() {
+        assert_eq!(
+            "xx".parse::<Lang>().unwrap_err(),
+            TranslationRecordError::UnsupportedLang("xx".to_string())
+        );
+    }
+
     #[test]
     fn word_new_ok_input_constructed() {
         let word = Word::new("chien", &Lang::fr);
@@ -219,6 +454,48 @@ mod tests {
         assert_eq!(err_word.unwrap_err(), TranslationRecordError::EmptyWord);
     }
 
+    #[test]
+    fn word_new_canonical_form_taken() {
+        let word = Word::new(" Pomme  de\tterre ", &Lang::fr).unwrap();
+        let decomposed = Word::new("e\u{301}le\u{300}ve", &Lang::fr).unwrap();
+
+        assert_eq!(word.value().0, "Pomme de terre");
+        assert_eq!(word.search_key(), "pomme de terre");
+        assert_eq!(decomposed.value().0, "élève");
+        assert_eq!(
+            Word::new(" \n ", &Lang::fr).unwrap_err(),
+            TranslationRecordError::Blank
+        );
+    }
+
+    #[test]
+    fn word_new_article_stripped_into_gender() {
+        let chien = Word::new("Le chien", &Lang::fr).unwrap();
+        let hund = Word::new("der  Hund", &Lang::de).unwrap();
+        let homme = Word::new("l'homme", &Lang::fr).unwrap();
+
+        assert_eq!(chien.value().0, "chien");
+        assert_eq!(chien.gender(), Some(Gender::Masculine));
+        assert_eq!(hund.value().0, "Hund");
+        assert_eq!(hund.gender(), Some(Gender::Masculine));
+        assert_eq!(homme.value().0, "homme");
+        assert_eq!(homme.gender(), None);
+        assert_eq!(
+            chien.search_key(),
+            Word::new("chien", &Lang::fr).unwrap().search_key()
+        );
+    }
+
+    #[test]
+    fn word_new_article_alone_or_inside_kept() {
+        let die = Word::new("die", &Lang::de).unwrap();
+        let lent = Word::new("lent", &Lang::fr).unwrap();
+
+        assert_eq!(die.value().0, "die");
+        assert_eq!(die.gender(), None);
+        assert_eq!(lent.value().0, "lent");
+    }
+
     #[test]
     fn translation_new_ok_input_constructed() {
         let words = vec!["hund", "köter"];
@@ -241,6 +518,88 @@ mod tests {
         );
     }
 
+    #[test]
+    fn translation_new_words_canonical_and_blank_err() {
+        let translations = Translations::new(&[" hund ", "kleiner  hund"], &Lang::de).unwrap();
+
+        assert_eq!(translations.translations(), &["hund", "kleiner hund"]);
+        assert_eq!(
+            Translations::new(&["hund", "  "], &Lang::de).unwrap_err(),
+            TranslationRecordError::Blank
+        );
+    }
+
+    #[test]
+    fn word_and_translations_illegal_or_overlong_err() {
+        let overlong = "a".repeat(text::MAX_LEN + 1);
+
+        assert_eq!(
+            Word::new("chi\u{200B}en", &Lang::fr).unwrap_err(),
+            TranslationRecordError::IllegalCharacter('\u{200B}')
+        );
+        assert_eq!(
+            Word::new(&overlong, &Lang::fr).unwrap_err(),
+            TranslationRecordError::TooLong
+        );
+        assert!(Word::new(&"a".repeat(text::MAX_LEN), &Lang::fr).is_ok());
+        assert_eq!(
+            Translations::new(&["hund", "k\u{0}ter"], &Lang::de).unwrap_err(),
+            TranslationRecordError::IllegalCharacter('\u{0}')
+        );
+    }
+
+    #[test]
+    fn translation_record_sense_canonical_and_folded() {
+        let lawyer = stub_translation_record(false)
+            .with_sense(Some(" Avocat  ou juge "))
+            .unwrap();
+        let unlabeled = stub_translation_record(false).with_sense(Some("")).unwrap();
+
+        assert_eq!(lawyer.sense(), Some("Avocat ou juge"));
+        assert!(lawyer.is_sense(Some("avocat ou JUGE")));
+        assert!(!lawyer.is_sense(None));
+        assert_eq!(unlabeled.sense(), None);
+        assert_eq!(
+            stub_translation_record(false)
+                .with_sense(Some(" "))
+                .unwrap_err(),
+            TranslationRecordError::Blank
+        );
+    }
+
+    #[test]
+    fn select_sense_by_label_or_single() {
+        let sense = |label: Option<&str>| stub_translation_record(false).with_sense(label).unwrap();
+        let fruit = sense(Some("fruit"));
+        let homographs = vec![sense(Some("lawyer")), fruit.clone()];
+
+        assert_eq!(
+            select_sense(homographs.clone(), Some("Fruit")),
+            Ok(fruit.clone())
+        );
+        assert_eq!(select_sense(vec![fruit.clone()], None), Ok(fruit));
+        assert_eq!(
+            select_sense(homographs.clone(), None),
+            Err(TranslationRecordError::AmbiguousSense)
+        );
+        assert_eq!(
+            select_sense(homographs, Some("judge")),
+            Err(TranslationRecordError::UnknownSense("judge".to_string()))
+        );
+    }
+
+    #[test]
+    fn translation_record_update_illegal_translation_err() {
+        let mut tr = stub_translation_record(true);
+
+        let result = tr.update(vec!["\u{FEFF}".to_string()], Lang::de);
+
+        assert_eq!(
+            result.unwrap_err(),
+            TranslationRecordError::IllegalCharacter('\u{FEFF}')
+        );
+    }
+
     #[test]
     fn translation_new_empty_string_err() {
         let err_words: [&str; 0] = [];
@@ -261,11 +620,20 @@ mod tests {
         let translations = vec!["hund", "köter"];
         let translation_lang = Lang::de;
 
-        let chien =
-            TranslationRecord::new(Some(id), word, &word_lang, &translations, &translation_lang)
-                .unwrap();
+        let owner = UserId::new("alice");
+
+        let chien = TranslationRecord::new(
+            Some(id),
+            &owner,
+            word,
+            &word_lang,
+            &translations,
+            &translation_lang,
+        )
+        .unwrap();
 
         assert_eq!(*chien.id.value(), Some(id.to_string()));
+        assert_eq!(chien.owner, owner);
         assert_eq!(chien.word.word, "chien");
         assert_eq!(chien.word.lang, Lang::fr);
         assert_eq!(chien.translations.lang, Lang::de);
@@ -282,8 +650,14 @@ mod tests {
         let translations = vec!["hund", ""];
         let translation_lang = Lang::de;
 
-        let chien =
-            TranslationRecord::new(None, word, &word_lang, &translations, &translation_lang);
+        let chien = TranslationRecord::new(
+            None,
+            &UserId::anonymous(),
+            word,
+            &word_lang,
+            &translations,
+            &translation_lang,
+        );
 
         assert!(chien.is_err());
         assert_eq!(
@@ -0,0 +1,309 @@
+use serde::Deserialize;
+use std::fs::read_to_string;
+use std::io;
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+use crate::domain;
+use crate::domain::create_translation::CreateError;
+use crate::domain::deck::{Access, Deck, DeckId};
+use crate::domain::ports::{
+    DeckRepository, LinkRepository, ProgressRepository, RepoReadError, RevisionRepository,
+    TranslationRepository, UserRepository,
+};
+use crate::domain::revision::{Revision, RevisionId};
+use crate::domain::user::UserId;
+use crate::domain::voci::{Lang, TranslationId, TranslationRecord};
+use crate::driving::cli::errors::CliError;
+#[cfg(feature = "client")]
+use crate::driving::cli::quiz::RemoteQuiz;
+use crate::driving::cli::quiz::{LocalQuiz, run_quiz};
+use crate::driving::cli::{Command, DeckCommand};
+
+/// One entry of an import file, same shape as the body of a create request
+#[derive(Debug, Deserialize)]
+struct ImportEntry {
+    word: String,
+    lang: Lang,
+    #[serde(default)]
+    sense: Option<String>,
+    translations: Vec<String>,
+    translation_lang: Lang,
+}
+
+/// Runs a non-server command directly against the repository
+///
+/// Commands work on the vocabulary of `user`, or the anonymous one if no user is given,
+/// or on the vocabulary of `deck` if given.
+/// `Command::Serve` is expected to be handled by the caller and is a no-op here.
+pub async fn run(
+    repository: &(
+         impl TranslationRepository
+         + UserRepository
+         + DeckRepository
+         + ProgressRepository
+         + LinkRepository
+         + RevisionRepository
+     ),
+    user: Option<&str>,
+    deck: Option<&str>,
+    command: Command,
+) -> Result<(), CliError> {
+    if let Command::Register { name } = &command {
+        return register(repository, name).await;
+    }
+    let owner = resolve_owner(repository, user).await?;
+    if let Command::Deck { command } = command {
+        return manage_deck(repository, &owner, command).await;
+    }
+    let deck = deck.map(DeckId::new);
+    let access = &domain::deck::resolve_access(repository, &owner, deck.as_ref()).await?;
+
+    match command {
+        Command::Serve { .. } | Command::Register { .. } | Command::Deck { .. } => Ok(()),
+        Command::Add {
+            word,
+            lang,
+            translation_lang,
+            sense,
+            translations,
+        } => {
+            let tr = domain::create_translation::create_translation(
+                repository,
+                access,
+                &word,
+                &lang,
+                sense.as_deref(),
+                &translations,
+                &translation_lang,
+            )
+            .await?;
+            println!("{}", format_record(&tr));
+            Ok(())
+        }
+        Command::Show { word, lang } => {
+            let senses =
+                domain::read_translation::read_translation(repository, access, &word, &lang, None)
+                    .await?;
+            for tr in &senses {
+                println!("{}", format_record(tr));
+            }
+            Ok(())
+        }
+        Command::Rm { word, lang, sense } => {
+            domain::delete_translation::delete_translation(
+                repository,
+                access,
+                &word,
+                &lang,
+                sense.as_deref(),
+            )
+            .await?;
+            println!("Moved {word} ({lang}) to the trash");
+            Ok(())
+        }
+        Command::List => {
+            let records = domain::list_translations::list_translations(repository, access).await?;
+            for tr in &records {
+                println!("{}", format_record(tr));
+            }
+            Ok(())
+        }
+        Command::Trash => {
+            let records = domain::trash::list_trash(repository, access).await?;
+            for tr in &records {
+                let id = tr.id().value().as_deref().unwrap_or_default();
+                println!("{id} {}", format_record(tr));
+            }
+            Ok(())
+        }
+        Command::Restore { id } => {
+            let tr = domain::trash::restore_translation(
+                repository,
+                access,
+                &TranslationId::from(id.as_str()),
+            )
+            .await?;
+            println!("Restored {}", format_record(&tr));
+            Ok(())
+        }
+        Command::History { id } => {
+            let revisions = domain::revision::translation_history(
+                repository,
+                access,
+                &TranslationId::from(id.as_str()),
+            )
+            .await?;
+            for revision in &revisions {
+                println!("{}", format_revision(revision));
+            }
+            Ok(())
+        }
+        Command::Revert { id, revision } => {
+            let tr = domain::revision::revert_translation(
+                repository,
+                access,
+                &TranslationId::from(id.as_str()),
+                &RevisionId::new(&revision),
+            )
+            .await?;
+            println!("Reverted {}", format_record(&tr));
+            Ok(())
+        }
+        Command::Search { query, lang } => {
+            let records = domain::search_translations::search_translations(
+                repository,
+                access,
+                &query,
+                lang.as_ref(),
+            )
+            .await?;
+            for tr in &records {
+                println!("{}", format_record(tr));
+            }
+            Ok(())
+        }
+        Command::Import { file } => import(repository, access, &file).await,
+        Command::Quiz {
+            size,
+            server,
+            api_key,
+            require_article,
+        } => {
+            let mut input = io::stdin().lock();
+            let mut output = io::stdout();
+            match (server, api_key) {
+                #[cfg(feature = "client")]
+                (Some(url), api_key) => {
+                    let source = RemoteQuiz::new(&url, api_key.as_deref(), require_article);
+                    run_quiz(&source, size, &mut input, &mut output).await
+                }
+                #[cfg(not(feature = "client"))]
+                (Some(_), _) => Err(CliError::RemoteUnavailable),
+                (None, _) => {
+                    let source = LocalQuiz::new(repository, access, require_article);
+                    run_quiz(&source, size, &mut input, &mut output).await
+                }
+            }?;
+            Ok(())
+        }
+    }
+}
+
+async fn resolve_owner(
+    repository: &impl UserRepository,
+    user: Option<&str>,
+) -> Result<UserId, CliError> {
+    let Some(name) = user else {
+        return Ok(UserId::anonymous());
+    };
+    match repository.read_user_by_name(name).await {
+        Ok(user) => Ok(user.id().clone()),
+        Err(RepoReadError::NotFound) => Err(CliError::UnknownUser(name.to_string())),
+        Err(e) => Err(CliError::Account(e.into())),
+    }
+}
+
+async fn manage_deck(
+    repository: &(impl DeckRepository + UserRepository),
+    user: &UserId,
+    command: DeckCommand,
+) -> Result<(), CliError> {
+    let deck = match command {
+        DeckCommand::Create { name } => domain::deck::create_deck(repository, user, &name).await?,
+        DeckCommand::AddEditor { deck, name } => {
+            domain::deck::add_editor(repository, user, &DeckId::new(&deck), &name).await?
+        }
+        DeckCommand::Subscribe { deck } => {
+            domain::deck::subscribe(repository, user, &DeckId::new(&deck)).await?
+        }
+        DeckCommand::Unsubscribe { deck } => {
+            domain::deck::unsubscribe(repository, user, &DeckId::new(&deck)).await?
+        }
+    };
+    println!("{}", format_deck(&deck));
+    Ok(())
+}
+
+async fn register(repository: &impl UserRepository, name: &str) -> Result<(), CliError> {
+    let mut output = io::stdout();
+    write!(output, "Password: ")?;
+    output.flush()?;
+    let mut password = String::new();
+    io::stdin().lock().read_line(&mut password)?;
+    let password = password.trim_end_matches(['\r', '\n']);
+
+    let user = domain::account::register(repository, name, password).await?;
+    println!("Registered {}", user.name());
+    Ok(())
+}
+
+async fn import(
+    repository: &(impl TranslationRepository + RevisionRepository),
+    access: &Access,
+    file: &Path,
+) -> Result<(), CliError> {
+    let entries: Vec<ImportEntry> = serde_json::from_str(&read_to_string(file)?)?;
+
+    let mut imported = 0;
+    let mut skipped = 0;
+    let mut failed = 0;
+
+    for entry in entries {
+        let result = domain::create_translation::create_translation(
+            repository,
+            access,
+            &entry.word,
+            &entry.lang,
+            entry.sense.as_deref(),
+            &entry.translations,
+            &entry.translation_lang,
+        )
+        .await;
+
+        match result {
+            Ok(_) => imported += 1,
+            Err(CreateError::Duplicate) => skipped += 1,
+            Err(e) => {
+                eprintln!("Cannot import {} ({}): {e}", entry.word, entry.lang);
+                failed += 1;
+            }
+        }
+    }
+
+    println!("Imported {imported}, skipped {skipped} duplicates, {failed} failed");
+    Ok(())
+}
+
+fn format_deck(deck: &Deck) -> String {
+    format!(
+        "{} ({}): {} editors, {} subscribers",
+        deck.name(),
+        deck.id(),
+        deck.editors().len() + 1,
+        deck.subscribers().len()
+    )
+}
+
+fn format_revision(revision: &Revision) -> String {
+    let id = revision.id().map(RevisionId::value).unwrap_or_default();
+    let record = revision
+        .after()
+        .or(revision.before())
+        .map(format_record)
+        .unwrap_or_default();
+    format!(
+        "{id} {} by {}: {record}",
+        revision.change(),
+        revision.actor()
+    )
+}
+
+fn format_record(tr: &TranslationRecord) -> String {
+    let (_, word, lang, translations, translation_lang) = tr.flat();
+    let sense = tr.sense().map(|s| format!(" [{s}]")).unwrap_or_default();
+    format!(
+        "{word}{sense} ({lang}) -> {} ({translation_lang})",
+        translations.join(", ")
+    )
+}
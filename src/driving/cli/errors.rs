@@ -0,0 +1,48 @@
+use thiserror::Error;
+
+use crate::domain::account::AccountError;
+use crate::domain::create_translation::CreateError;
+use crate::domain::deck::DeckError;
+use crate::domain::delete_translation::DeleteError;
+use crate::domain::list_translations::ListError;
+use crate::domain::quiz::QuizError;
+use crate::domain::read_translation::ReadError;
+use crate::domain::revision::RevisionError;
+use crate::domain::search_translations::SearchError;
+use crate::domain::trash::TrashError;
+
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error("{0}")]
+    Create(#[from] CreateError),
+    #[error("{0}")]
+    Read(#[from] ReadError),
+    #[error("{0}")]
+    Delete(#[from] DeleteError),
+    #[error("{0}")]
+    List(#[from] ListError),
+    #[error("{0}")]
+    Search(#[from] SearchError),
+    #[error("{0}")]
+    Trash(#[from] TrashError),
+    #[error("{0}")]
+    Revision(#[from] RevisionError),
+    #[error("{0}")]
+    Quiz(#[from] QuizError),
+    #[error("{0}")]
+    Account(#[from] AccountError),
+    #[error("{0}")]
+    Deck(#[from] DeckError),
+    #[error("Unknown user {0}, register it first")]
+    UnknownUser(String),
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[cfg(feature = "client")]
+    #[error("{0}")]
+    Remote(#[from] crate::client::ClientError),
+    #[cfg(not(feature = "client"))]
+    #[error("Built without the client feature, cannot reach a server")]
+    RemoteUnavailable,
+    #[error("Cannot parse import file: {0}")]
+    ImportFormat(#[from] serde_json::Error),
+}
@@ -0,0 +1,230 @@
+pub mod commands;
+pub mod errors;
+pub mod quiz;
+
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use crate::domain::voci::Lang;
+
+/// Practice vocabulary from and to translations
+#[derive(Debug, Parser)]
+#[command(name = "vocabulaire", version, about)]
+pub struct Cli {
+    /// Base config file, defaults to VOCI_CONFIG
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+
+    /// Work on the vocabulary of this user instead of the anonymous one
+    #[arg(long, global = true)]
+    pub user: Option<String>,
+
+    /// Work on the deck with this ID, the user must edit or subscribe to it
+    #[arg(long, global = true, requires = "user")]
+    pub deck: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, PartialEq, Subcommand)]
+pub enum Command {
+    /// Start the REST server
+    Serve {
+        /// Overrides the port of the server configuration
+        #[arg(long)]
+        port: Option<u16>,
+    },
+    /// Add a word together with its translations
+    Add {
+        word: String,
+        lang: Lang,
+        #[arg(long = "to")]
+        translation_lang: Lang,
+        /// Sense of a word with several meanings, e.g. "fruit" for "avocat"
+        #[arg(long)]
+        sense: Option<String>,
+        #[arg(required = true)]
+        translations: Vec<String>,
+    },
+    /// Show the translations of a word, of all its senses
+    Show { word: String, lang: Lang },
+    /// Move a word and its translations to the trash
+    Rm {
+        word: String,
+        lang: Lang,
+        /// Sense to remove, needed if the word has several
+        #[arg(long)]
+        sense: Option<String>,
+    },
+    /// List all words with their translations
+    List,
+    /// List the words in the trash with their IDs
+    Trash,
+    /// Take a word out of the trash by its ID
+    Restore { id: String },
+    /// List the revisions of a word by its ID, oldest first
+    History { id: String },
+    /// Set a word back to its state after one of its revisions
+    Revert { id: String, revision: String },
+    /// Search words and translations by prefix or part, ignoring case and accents
+    Search {
+        query: String,
+        /// Only match words or translations of this language
+        #[arg(long)]
+        lang: Option<Lang>,
+    },
+    /// Import words from a JSON file holding an array of translation records
+    Import { file: PathBuf },
+    /// Create a user account, the password is read from stdin
+    Register { name: String },
+    /// Share vocabulary in decks, needs `--user`
+    Deck {
+        #[command(subcommand)]
+        command: DeckCommand,
+    },
+    /// Practice a randomized batch of words
+    Quiz {
+        #[arg(long, default_value_t = 10)]
+        size: usize,
+        /// Practice against a running server instead, e.g. http://localhost:8082
+        #[arg(long)]
+        server: Option<String>,
+        /// API key sent to the server, if it protects reads
+        #[arg(long, requires = "server")]
+        api_key: Option<String>,
+        /// Count answers as correct only with the article of the translation, e.g. "der Hund"
+        #[arg(long)]
+        require_article: bool,
+    },
+}
+
+#[derive(Debug, PartialEq, Subcommand)]
+pub enum DeckCommand {
+    /// Create a deck owned by the user
+    Create { name: String },
+    /// Let another user edit a deck of the user
+    AddEditor { deck: String, name: String },
+    /// Read the words of a deck along with the own ones
+    Subscribe { deck: String },
+    /// Stop reading the words of a deck
+    Unsubscribe { deck: String },
+}
+
+impl Default for Command {
+    fn default() -> Self {
+        Command::Serve { port: None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_no_subcommand_none() {
+        let cli = Cli::try_parse_from(["vocabulaire"]).unwrap();
+
+        assert_eq!(cli.command, None);
+    }
+
+    #[test]
+    fn parse_add_with_translations() {
+        let cli = Cli::try_parse_from([
+            "vocabulaire",
+            "add",
+            "chien",
+            "fr",
+            "--to",
+            "de",
+            "hund",
+            "köter",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            cli.command,
+            Some(Command::Add {
+                word: "chien".to_string(),
+                lang: Lang::fr,
+                translation_lang: Lang::de,
+                sense: None,
+                translations: vec!["hund".to_string(), "köter".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn parse_rm_with_sense() {
+        let cli =
+            Cli::try_parse_from(["vocabulaire", "rm", "avocat", "fr", "--sense", "fruit"]).unwrap();
+
+        assert_eq!(
+            cli.command,
+            Some(Command::Rm {
+                word: "avocat".to_string(),
+                lang: Lang::fr,
+                sense: Some("fruit".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_global_config_after_subcommand() {
+        let cli = Cli::try_parse_from(["vocabulaire", "list", "--config", "voci.toml"]).unwrap();
+
+        assert_eq!(cli.config, Some(PathBuf::from("voci.toml")));
+        assert_eq!(cli.command, Some(Command::List));
+    }
+
+    #[test]
+    fn parse_global_user() {
+        let cli = Cli::try_parse_from(["vocabulaire", "--user", "alice", "list"]).unwrap();
+
+        assert_eq!(cli.user, Some("alice".to_string()));
+        assert_eq!(cli.command, Some(Command::List));
+    }
+
+    #[test]
+    fn parse_deck_subscribe() {
+        let cli = Cli::try_parse_from([
+            "vocabulaire",
+            "--user",
+            "bob",
+            "deck",
+            "subscribe",
+            "deck-1",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            cli.command,
+            Some(Command::Deck {
+                command: DeckCommand::Subscribe {
+                    deck: "deck-1".to_string()
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn parse_deck_without_user_err() {
+        let cli = Cli::try_parse_from(["vocabulaire", "--deck", "deck-1", "list"]);
+
+        assert!(cli.is_err());
+    }
+
+    #[test]
+    fn parse_add_without_translations_err() {
+        let cli = Cli::try_parse_from(["vocabulaire", "add", "chien", "fr", "--to", "de"]);
+
+        assert!(cli.is_err());
+    }
+
+    #[test]
+    fn parse_unsupported_lang_err() {
+        let cli = Cli::try_parse_from(["vocabulaire", "show", "chien", "xx"]);
+
+        assert!(cli.is_err());
+    }
+}
@@ -0,0 +1,209 @@
+use async_trait::async_trait;
+use std::io::{BufRead, Write};
+
+#[cfg(feature = "client")]
+use crate::client::{QuizAnswerRequest, VociClient};
+use crate::domain;
+use crate::domain::deck::Access;
+use crate::domain::ports::{LinkRepository, ProgressRepository, TranslationRepository};
+use crate::domain::quiz::Grade;
+use crate::driving::cli::errors::CliError;
+use crate::driving::rest_handler::quiz::QuizQuestion;
+
+/// Where quiz questions come from and where answers get graded
+#[async_trait(?Send)]
+pub trait QuizSource {
+    async fn draw(&self, size: usize) -> Result<Vec<QuizQuestion>, CliError>;
+
+    async fn answer(&self, question: &QuizQuestion, answer: &str) -> Result<Grade, CliError>;
+}
+
+/// Practice the readable vocabulary of `access` directly against the configured repository
+pub struct LocalQuiz<'a, R: TranslationRepository + ProgressRepository + LinkRepository> {
+    repository: &'a R,
+    access: &'a Access,
+    require_article: bool,
+}
+
+impl<'a, R: TranslationRepository + ProgressRepository + LinkRepository> LocalQuiz<'a, R> {
+    pub fn new(repository: &'a R, access: &'a Access, require_article: bool) -> Self {
+        LocalQuiz {
+            repository,
+            access,
+            require_article,
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl<R: TranslationRepository + ProgressRepository + LinkRepository> QuizSource
+    for LocalQuiz<'_, R>
+{
+    async fn draw(&self, size: usize) -> Result<Vec<QuizQuestion>, CliError> {
+        let records = domain::quiz::draw_quiz(self.repository, self.access, size).await?;
+        Ok(records.into_iter().map(QuizQuestion::from).collect())
+    }
+
+    async fn answer(&self, question: &QuizQuestion, answer: &str) -> Result<Grade, CliError> {
+        let grade = domain::quiz::check_answer(
+            self.repository,
+            self.access,
+            &question.word,
+            &question.lang,
+            answer,
+            self.require_article,
+        )
+        .await?;
+        Ok(grade)
+    }
+}
+
+/// Practice against a running server over the REST API
+#[cfg(feature = "client")]
+pub struct RemoteQuiz {
+    client: VociClient,
+    require_article: bool,
+}
+
+#[cfg(feature = "client")]
+impl RemoteQuiz {
+    /// `server` is the base url of the server, e.g. `http://localhost:8082`
+    pub fn new(server: &str, api_key: Option<&str>, require_article: bool) -> Self {
+        let client = VociClient::new(server);
+        RemoteQuiz {
+            client: match api_key {
+                Some(key) => client.with_api_key(key),
+                None => client,
+            },
+            require_article,
+        }
+    }
+}
+
+#[cfg(feature = "client")]
+#[async_trait(?Send)]
+impl QuizSource for RemoteQuiz {
+    async fn draw(&self, size: usize) -> Result<Vec<QuizQuestion>, CliError> {
+        Ok(self.client.draw_quiz(size).await?)
+    }
+
+    async fn answer(&self, question: &QuizQuestion, answer: &str) -> Result<Grade, CliError> {
+        let request = QuizAnswerRequest {
+            word: question.word.clone(),
+            lang: question.lang.clone(),
+            answer: answer.to_string(),
+            require_article: self.require_article,
+        };
+        let response = self.client.answer_quiz(&request).await?;
+        Ok(Grade {
+            correct: response.correct,
+            translations: response.translations,
+            progress: response.progress,
+        })
+    }
+}
+
+/// Number of correct answers out of the number of questions asked
+#[derive(Debug, PartialEq)]
+pub struct Score {
+    pub correct: usize,
+    pub total: usize,
+}
+
+/// Prompts for each word of a randomized batch and grades the typed answers
+///
+/// The correct translations are shown on a miss, the score is printed at the end.
+pub async fn run_quiz(
+    source: &impl QuizSource,
+    size: usize,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> Result<Score, CliError> {
+    let questions = source.draw(size).await?;
+    let mut score = Score {
+        correct: 0,
+        total: 0,
+    };
+
+    for question in &questions {
+        write!(
+            output,
+            "{} ({}) -> {}: ",
+            question.word, question.lang, question.translation_lang
+        )?;
+        output.flush()?;
+
+        let mut answer = String::new();
+        if input.read_line(&mut answer)? == 0 {
+            break;
+        }
+
+        let grade = source.answer(question, &answer).await?;
+        score.total += 1;
+        if grade.correct {
+            score.correct += 1;
+            writeln!(output, "correct")?;
+        } else {
+            writeln!(output, "wrong, it is: {}", grade.translations.join(", "))?;
+        }
+    }
+
+    writeln!(output, "Score: {}/{}", score.correct, score.total)?;
+    Ok(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::test_utils::{utils::shared::*, voci_repo_double::repo_double::VociRepoDouble};
+
+    #[actix_rt::test]
+    async fn run_quiz_correct_answer_scored() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        let mut input = Cursor::new(format!("{}\n", TRANSLATIONS[0]));
+        let mut output = Vec::new();
+
+        let score = run_quiz(
+            &LocalQuiz::new(&repo, &access(), false),
+            10,
+            &mut input,
+            &mut output,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            score,
+            Score {
+                correct: 1,
+                total: 1
+            }
+        );
+        assert!(String::from_utf8(output).unwrap().ends_with("Score: 1/1\n"));
+    }
+
+    #[actix_rt::test]
+    async fn run_quiz_wrong_answer_shows_translations() {
+        let repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        let mut input = Cursor::new("katze\n");
+        let mut output = Vec::new();
+
+        let score = run_quiz(
+            &LocalQuiz::new(&repo, &access(), false),
+            10,
+            &mut input,
+            &mut output,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(score.correct, 0);
+        assert!(
+            String::from_utf8(output)
+                .unwrap()
+                .contains(&TRANSLATIONS.join(", "))
+        );
+    }
+}
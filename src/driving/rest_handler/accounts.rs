@@ -0,0 +1,144 @@
+use actix_web::web;
+use actix_web::web::Json;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+use utoipa::ToSchema;
+use validator::Validate;
+
+use crate::domain;
+use crate::domain::account::AccountError;
+use crate::domain::ports::UserRepository;
+use crate::domain::user::User;
+
+use crate::driving::rest_handler::errors::{ApiError, Problem};
+use crate::driving::rest_handler::session::TokenIssuer;
+use crate::driving::rest_handler::validate::validate;
+
+#[derive(Clone, Deserialize, Serialize, Validate, ToSchema)]
+pub struct CredentialsRequest {
+    #[validate(length(
+        min = 1,
+        max = 64,
+        message = "Name is required and must be at most 64 characters"
+    ))]
+    #[schema(min_length = 1, max_length = 64)]
+    pub name: String,
+    #[validate(length(min = 8, message = "Password must be at least 8 characters"))]
+    #[schema(min_length = 8)]
+    pub password: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, ToSchema)]
+pub struct UserResponse {
+    pub id: String,
+    pub name: String,
+}
+impl From<User> for UserResponse {
+    fn from(u: User) -> Self {
+        UserResponse {
+            id: u.id().value().to_string(),
+            name: u.name().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, ToSchema)]
+pub struct TokenResponse {
+    pub access_token: String,
+    /// Always `Bearer`
+    pub token_type: String,
+    /// Lifetime of the token in seconds
+    pub expires_in: u64,
+}
+
+fn map_account_error(e: AccountError) -> ApiError {
+    match e {
+        AccountError::InvalidName | AccountError::WeakPassword => ApiError::invalid_input(&e),
+        AccountError::NameTaken => ApiError::conflict(&e),
+        AccountError::InvalidCredentials => ApiError::unauthorized(&e),
+        AccountError::Read(_) | AccountError::Create(_) | AccountError::Hash(_) => {
+            ApiError::unknown(&e)
+        }
+    }
+}
+
+/// Create a user account
+#[utoipa::path(
+    post,
+    path = "/voci/api/v1/users",
+    tag = "users",
+    request_body = CredentialsRequest,
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "User created", body = UserResponse),
+        (
+            status = 400, description = "Invalid name or password",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "API key missing or unknown",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "API key lacks the admin scope",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 409, description = "Name is taken",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 422, description = "Validation failed",
+            body = Problem, content_type = "application/problem+json"
+        ),
+    )
+)]
+#[instrument(skip_all, fields(name = %request.name))]
+pub async fn register<T: UserRepository>(
+    repository: web::Data<T>,
+    request: Json<CredentialsRequest>,
+) -> Result<Json<UserResponse>, ApiError> {
+    validate(&request)?;
+
+    domain::account::register(repository.get_ref(), &request.name, &request.password)
+        .await
+        .map(|u| Json(UserResponse::from(u)))
+        .map_err(map_account_error)
+}
+
+/// Log in for a bearer token, further requests carrying it work on the user's vocabulary
+#[utoipa::path(
+    post,
+    path = "/voci/api/v1/login",
+    tag = "users",
+    request_body = CredentialsRequest,
+    responses(
+        (status = 200, description = "Logged in", body = TokenResponse),
+        (
+            status = 401, description = "Invalid name or password",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 500, description = "Unknown error",
+            body = Problem, content_type = "application/problem+json"
+        ),
+    )
+)]
+#[instrument(skip_all, fields(name = %request.name))]
+pub async fn login<T: UserRepository>(
+    repository: web::Data<T>,
+    issuer: web::Data<TokenIssuer>,
+    request: Json<CredentialsRequest>,
+) -> Result<Json<TokenResponse>, ApiError> {
+    let user = domain::account::login(repository.get_ref(), &request.name, &request.password)
+        .await
+        .map_err(map_account_error)?;
+
+    let access_token = issuer.issue(&user).map_err(|e| ApiError::unknown(&e))?;
+
+    Ok(Json(TokenResponse {
+        access_token,
+        token_type: "Bearer".to_string(),
+        expires_in: issuer.ttl(),
+    }))
+}
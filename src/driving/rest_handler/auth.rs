@@ -0,0 +1,183 @@
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::HeaderName;
+use actix_web::middleware::Next;
+use actix_web::{Error, web};
+use tracing::debug;
+
+use crate::config::AuthConfig;
+use crate::domain;
+use crate::domain::auth::{AuthError, Scope};
+use crate::driving::rest_handler::errors::ApiError;
+
+pub const API_KEY_HEADER: HeaderName = HeaderName::from_static("x-api-key");
+
+/// Requires a key with `read` scope for safe methods and `write` scope for all others
+pub async fn authorize(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, Error> {
+    let required = if req.method().is_safe() {
+        Scope::Read
+    } else {
+        Scope::Write
+    };
+    respond(req, next, required).await
+}
+
+/// Requires a key with `read` scope, for POST endpoints that change nothing
+pub async fn authorize_read(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, Error> {
+    respond(req, next, Scope::Read).await
+}
+
+/// Requires a key with `admin` scope, for administrative endpoints
+pub async fn authorize_admin(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, Error> {
+    respond(req, next, Scope::Admin).await
+}
+
+/// Rejections are turned into responses right away, so outer middleware sees their status
+async fn respond(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+    required: Scope,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, Error> {
+    match check(&req, required) {
+        Ok(()) => Ok(next.call(req).await?.map_into_left_body()),
+        Err(e) => Ok(req.error_response(e).map_into_right_body()),
+    }
+}
+
+fn check(req: &ServiceRequest, required: Scope) -> Result<(), ApiError> {
+    let Some(auth) = req.app_data::<web::Data<AuthConfig>>() else {
+        return Ok(());
+    };
+    if !auth.enabled() || (required == Scope::Read && !auth.protect_reads) {
+        return Ok(());
+    }
+
+    let key = req
+        .headers()
+        .get(&API_KEY_HEADER)
+        .and_then(|key| key.to_str().ok());
+
+    match domain::auth::authorize(&auth.keys, key, required) {
+        Ok(api_key) => {
+            debug!(key = %api_key.name, scope = %required, "authorized");
+            Ok(())
+        }
+        Err(e @ AuthError::MissingScope(..)) => Err(ApiError::forbidden(&e)),
+        Err(e) => Err(ApiError::unauthorized(&e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use actix_web::middleware::from_fn;
+    use actix_web::{App, HttpResponse, test};
+
+    use super::*;
+    use crate::domain::auth::{ApiKey, hash_api_key};
+
+    async fn status_of(auth: AuthConfig, request: test::TestRequest) -> StatusCode {
+        let app = test::init_service(
+            App::new().app_data(web::Data::new(auth)).service(
+                web::resource("/")
+                    .wrap(from_fn(authorize))
+                    .route(web::get().to(HttpResponse::Ok))
+                    .route(web::delete().to(HttpResponse::Ok)),
+            ),
+        )
+        .await;
+
+        test::call_service(&app, request.to_request())
+            .await
+            .status()
+    }
+
+    fn auth(protect_reads: bool) -> AuthConfig {
+        AuthConfig {
+            keys: vec![
+                ApiKey {
+                    name: "reader".to_string(),
+                    hash: hash_api_key("r3ad"),
+                    scopes: vec![Scope::Read],
+                },
+                ApiKey {
+                    name: "writer".to_string(),
+                    hash: hash_api_key("wr1te"),
+                    scopes: vec![Scope::Write],
+                },
+                ApiKey {
+                    name: "admin".to_string(),
+                    hash: hash_api_key("adm1n"),
+                    scopes: vec![Scope::Admin],
+                },
+            ],
+            protect_reads,
+            ..AuthConfig::default()
+        }
+    }
+
+    #[actix_rt::test]
+    async fn writes_without_key_unauthorized() {
+        let status = status_of(auth(false), test::TestRequest::delete()).await;
+
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[actix_rt::test]
+    async fn writes_with_read_key_forbidden() {
+        let request = test::TestRequest::delete().insert_header((API_KEY_HEADER, "r3ad"));
+
+        assert_eq!(status_of(auth(false), request).await, StatusCode::FORBIDDEN);
+    }
+
+    #[actix_rt::test]
+    async fn admin_routes_need_admin_key() {
+        let app = test::init_service(
+            App::new().app_data(web::Data::new(auth(false))).service(
+                web::resource("/")
+                    .wrap(from_fn(authorize_admin))
+                    .route(web::post().to(HttpResponse::Ok)),
+            ),
+        )
+        .await;
+        let status_with = async |key: &str| {
+            let request = test::TestRequest::post().insert_header((API_KEY_HEADER, key));
+            test::call_service(&app, request.to_request())
+                .await
+                .status()
+        };
+
+        assert_eq!(status_with("wr1te").await, StatusCode::FORBIDDEN);
+        assert_eq!(status_with("adm1n").await, StatusCode::OK);
+    }
+
+    #[actix_rt::test]
+    async fn reads_protected_on_demand() {
+        assert_eq!(
+            status_of(auth(false), test::TestRequest::get()).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status_of(auth(true), test::TestRequest::get()).await,
+            StatusCode::UNAUTHORIZED
+        );
+        let request = test::TestRequest::get().insert_header((API_KEY_HEADER, "r3ad"));
+        assert_eq!(status_of(auth(true), request).await, StatusCode::OK);
+    }
+
+    #[actix_rt::test]
+    async fn no_keys_configured_open() {
+        let status = status_of(AuthConfig::default(), test::TestRequest::delete()).await;
+
+        assert_eq!(status, StatusCode::OK);
+    }
+}
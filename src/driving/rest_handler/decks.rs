@@ -0,0 +1,220 @@
+use actix_web::web;
+use actix_web::web::{Json, Path};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::domain;
+use crate::domain::deck::{Access, Deck, DeckError, DeckId};
+use crate::domain::ports::{DeckRepository, UserRepository};
+
+use crate::driving::rest_handler::errors::{ApiError, Problem};
+use crate::driving::rest_handler::session::Caller;
+use crate::driving::rest_handler::validate::validate;
+
+/// Selects a deck to work on instead of the own vocabulary
+#[derive(Clone, Debug, Deserialize, Serialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeckParam {
+    /// ID of a deck the caller edits or subscribed to
+    pub deck: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Validate, ToSchema)]
+pub struct CreateDeckRequest {
+    #[validate(length(
+        min = 1,
+        max = 100,
+        message = "Name is required and must be at most 100 characters"
+    ))]
+    #[schema(min_length = 1, max_length = 100)]
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Validate, ToSchema)]
+pub struct EditorRequest {
+    /// Name of the user to become editor
+    #[validate(length(min = 1, message = "Name is required"))]
+    #[schema(min_length = 1)]
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, ToSchema)]
+pub struct DeckResponse {
+    pub id: String,
+    pub name: String,
+    pub owner: String,
+    pub editors: Vec<String>,
+    pub subscribers: usize,
+}
+impl From<Deck> for DeckResponse {
+    fn from(d: Deck) -> Self {
+        DeckResponse {
+            id: d.id().value().to_string(),
+            name: d.name().to_string(),
+            owner: d.owner().value().to_string(),
+            editors: d.editors().iter().map(|e| e.value().to_string()).collect(),
+            subscribers: d.subscribers().len(),
+        }
+    }
+}
+
+pub(crate) fn map_deck_error(e: DeckError) -> ApiError {
+    match e {
+        DeckError::InvalidName => ApiError::invalid_input(&e),
+        DeckError::LoginRequired => ApiError::unauthorized(&e),
+        DeckError::NotFound | DeckError::UnknownUser(_) => ApiError::not_found(&e),
+        DeckError::NotOwner | DeckError::NotSubscribed => ApiError::forbidden(&e),
+        DeckError::Read(_) | DeckError::Create(_) | DeckError::Update(_) => ApiError::unknown(&e),
+    }
+}
+
+/// What the caller may read and write, their own vocabulary or the selected deck
+pub async fn resolve_access(
+    repository: &impl DeckRepository,
+    caller: &Caller,
+) -> Result<Access, ApiError> {
+    domain::deck::resolve_access(repository, &caller.user, caller.deck.as_ref())
+        .await
+        .map_err(map_deck_error)
+}
+
+/// Create a deck, its owner edits it with `?deck=<id>` on the translation endpoints
+#[utoipa::path(
+    post,
+    path = "/voci/api/v1/decks",
+    tag = "decks",
+    request_body = CreateDeckRequest,
+    security(("api_key" = []), ("bearer" = [])),
+    responses(
+        (status = 200, description = "Deck created", body = DeckResponse),
+        (
+            status = 400, description = "Invalid name",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "Not logged in",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 422, description = "Validation failed",
+            body = Problem, content_type = "application/problem+json"
+        ),
+    )
+)]
+#[instrument(skip_all, fields(name = %request.name))]
+pub async fn create_deck<T: DeckRepository>(
+    repository: web::Data<T>,
+    caller: Caller,
+    request: Json<CreateDeckRequest>,
+) -> Result<Json<DeckResponse>, ApiError> {
+    validate(&request)?;
+
+    domain::deck::create_deck(repository.get_ref(), &caller.user, &request.name)
+        .await
+        .map(|d| Json(DeckResponse::from(d)))
+        .map_err(map_deck_error)
+}
+
+/// Let another user edit a deck, only its owner may do so
+#[utoipa::path(
+    post,
+    path = "/voci/api/v1/decks/{id}/editors",
+    tag = "decks",
+    params(("id" = String, Path, description = "ID of the deck")),
+    request_body = EditorRequest,
+    security(("api_key" = []), ("bearer" = [])),
+    responses(
+        (status = 200, description = "Editor added", body = DeckResponse),
+        (
+            status = 403, description = "Not the owner of the deck",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Deck or user not found",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 422, description = "Validation failed",
+            body = Problem, content_type = "application/problem+json"
+        ),
+    )
+)]
+#[instrument(skip_all, fields(deck = %id, editor = %request.name))]
+pub async fn add_editor<T: DeckRepository + UserRepository>(
+    repository: web::Data<T>,
+    caller: Caller,
+    id: Path<String>,
+    request: Json<EditorRequest>,
+) -> Result<Json<DeckResponse>, ApiError> {
+    validate(&request)?;
+
+    domain::deck::add_editor(
+        repository.get_ref(),
+        &caller.user,
+        &DeckId::new(&id),
+        &request.name,
+    )
+    .await
+    .map(|d| Json(DeckResponse::from(d)))
+    .map_err(map_deck_error)
+}
+
+/// Subscribe to a deck, its records are then read along with the own ones
+#[utoipa::path(
+    put,
+    path = "/voci/api/v1/decks/{id}/subscription",
+    tag = "decks",
+    params(("id" = String, Path, description = "ID of the deck")),
+    security(("api_key" = []), ("bearer" = [])),
+    responses(
+        (status = 200, description = "Subscribed", body = DeckResponse),
+        (
+            status = 401, description = "Not logged in",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Deck not found",
+            body = Problem, content_type = "application/problem+json"
+        ),
+    )
+)]
+#[instrument(skip_all, fields(deck = %id))]
+pub async fn subscribe<T: DeckRepository>(
+    repository: web::Data<T>,
+    caller: Caller,
+    id: Path<String>,
+) -> Result<Json<DeckResponse>, ApiError> {
+    domain::deck::subscribe(repository.get_ref(), &caller.user, &DeckId::new(&id))
+        .await
+        .map(|d| Json(DeckResponse::from(d)))
+        .map_err(map_deck_error)
+}
+
+/// Unsubscribe from a deck
+#[utoipa::path(
+    delete,
+    path = "/voci/api/v1/decks/{id}/subscription",
+    tag = "decks",
+    params(("id" = String, Path, description = "ID of the deck")),
+    security(("api_key" = []), ("bearer" = [])),
+    responses(
+        (status = 200, description = "Unsubscribed", body = DeckResponse),
+        (
+            status = 404, description = "Deck not found",
+            body = Problem, content_type = "application/problem+json"
+        ),
+    )
+)]
+#[instrument(skip_all, fields(deck = %id))]
+pub async fn unsubscribe<T: DeckRepository>(
+    repository: web::Data<T>,
+    caller: Caller,
+    id: Path<String>,
+) -> Result<Json<DeckResponse>, ApiError> {
+    domain::deck::unsubscribe(repository.get_ref(), &caller.user, &DeckId::new(&id))
+        .await
+        .map(|d| Json(DeckResponse::from(d)))
+        .map_err(map_deck_error)
+}
@@ -1,32 +1,484 @@
 use actix_web::{HttpResponse, error::ResponseError, http::StatusCode};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Display;
 use thiserror::Error;
+use utoipa::ToSchema;
+
+use crate::domain::account::AccountError;
+use crate::domain::auth::AuthError;
+use crate::domain::conjugation::ConjugationError;
+use crate::domain::create_translation::CreateError;
+use crate::domain::deck::DeckError;
+use crate::domain::delete_translation::DeleteError;
+use crate::domain::link::LinkError;
+use crate::domain::list_translations::ListError;
+use crate::domain::ports::{RepoCreateError, RepoDeleteError, RepoReadError, RepoUpdateError};
+use crate::domain::quiz::QuizError;
+use crate::domain::read_translation::ReadError;
+use crate::domain::revision::RevisionError;
+use crate::domain::search_translations::SearchError;
+use crate::domain::trash::TrashError;
+use crate::domain::update_translation::UpdateError;
+use crate::domain::voci::TranslationRecordError;
+use crate::driving::rest_handler::session::SessionError;
+
+const PROBLEM_CONTENT_TYPE: &str = "application/problem+json";
+const VALIDATION_CODE: &str = "validation_failed";
+
+/// Stable, machine-readable identifier of an error, sent along as `code` in a `Problem`
+pub trait ErrorCode: Display {
+    fn code(&self) -> &'static str;
+}
+
+/// RFC 7807 problem details, the body of every error response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Problem {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub code: String,
+    pub detail: String,
+    /// Validation messages keyed by field name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub errors: Option<BTreeMap<String, Vec<String>>>,
+    /// Nearest existing words, when a word was not found
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggestions: Option<Vec<String>>,
+}
+
+impl Problem {
+    pub fn new(status: u16, code: &str, detail: &str) -> Self {
+        let title = StatusCode::from_u16(status)
+            .ok()
+            .and_then(|s| s.canonical_reason())
+            .unwrap_or_default();
+        Problem {
+            problem_type: "about:blank".to_string(),
+            title: title.to_string(),
+            status,
+            code: code.to_string(),
+            detail: detail.to_string(),
+            errors: None,
+            suggestions: None,
+        }
+    }
+}
 
 #[derive(Debug, PartialEq, Error)]
 pub enum ApiError {
     #[error("Request cannot be handled")]
-    BadRequest(String),
+    BadRequest { code: &'static str, detail: String },
     #[error("Item not found")]
-    NotFound(String),
+    NotFound {
+        code: &'static str,
+        detail: String,
+        suggestions: Vec<String>,
+    },
     #[error("Input Invalid")]
-    InvalidInput(String),
+    InvalidInput { code: &'static str, detail: String },
     #[error("Conflicting Item")]
-    Conflict(String),
+    Conflict { code: &'static str, detail: String },
+    #[error("Unauthorized")]
+    Unauthorized { code: &'static str, detail: String },
+    #[error("Forbidden")]
+    Forbidden { code: &'static str, detail: String },
     #[error("Validation Error")]
-    ValidationError(Vec<String>),
+    ValidationError(BTreeMap<String, Vec<String>>),
     #[error("Unknown")]
-    Unknown(String),
+    Unknown { code: &'static str, detail: String },
+}
+
+impl ApiError {
+    pub fn bad_request(e: &impl ErrorCode) -> Self {
+        ApiError::BadRequest {
+            code: e.code(),
+            detail: e.to_string(),
+        }
+    }
+
+    pub fn not_found(e: &impl ErrorCode) -> Self {
+        ApiError::NotFound {
+            code: e.code(),
+            detail: e.to_string(),
+            suggestions: Vec::new(),
+        }
+    }
+
+    /// Not found, offering the nearest existing words instead
+    pub fn not_found_with_suggestions(e: &impl ErrorCode, suggestions: &[String]) -> Self {
+        ApiError::NotFound {
+            code: e.code(),
+            detail: e.to_string(),
+            suggestions: suggestions.to_vec(),
+        }
+    }
+
+    pub fn invalid_input(e: &impl ErrorCode) -> Self {
+        ApiError::InvalidInput {
+            code: e.code(),
+            detail: e.to_string(),
+        }
+    }
+
+    pub fn conflict(e: &impl ErrorCode) -> Self {
+        ApiError::Conflict {
+            code: e.code(),
+            detail: e.to_string(),
+        }
+    }
+
+    pub fn unauthorized(e: &impl ErrorCode) -> Self {
+        ApiError::Unauthorized {
+            code: e.code(),
+            detail: e.to_string(),
+        }
+    }
+
+    pub fn forbidden(e: &impl ErrorCode) -> Self {
+        ApiError::Forbidden {
+            code: e.code(),
+            detail: e.to_string(),
+        }
+    }
+
+    pub fn unknown(e: &impl ErrorCode) -> Self {
+        ApiError::Unknown {
+            code: e.code(),
+            detail: e.to_string(),
+        }
+    }
+
+    pub fn problem(&self) -> Problem {
+        let status = self.status_code().as_u16();
+        match self {
+            ApiError::NotFound {
+                code,
+                detail,
+                suggestions,
+            } => Problem {
+                suggestions: (!suggestions.is_empty()).then(|| suggestions.clone()),
+                ..Problem::new(status, code, detail)
+            },
+            ApiError::BadRequest { code, detail }
+            | ApiError::InvalidInput { code, detail }
+            | ApiError::Conflict { code, detail }
+            | ApiError::Unauthorized { code, detail }
+            | ApiError::Forbidden { code, detail }
+            | ApiError::Unknown { code, detail } => Problem::new(status, code, detail),
+            ApiError::ValidationError(fields) => Problem {
+                errors: Some(fields.clone()),
+                ..Problem::new(status, VALIDATION_CODE, &self.to_string())
+            },
+        }
+    }
 }
 
 /// Automatically convert ApiErrors to external ResponseError
 impl ResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ApiError::BadRequest { .. } => StatusCode::BAD_REQUEST,
+            ApiError::NotFound { .. } => StatusCode::NOT_FOUND,
+            ApiError::InvalidInput { .. } => StatusCode::BAD_REQUEST,
+            ApiError::Conflict { .. } => StatusCode::CONFLICT,
+            ApiError::Unauthorized { .. } => StatusCode::UNAUTHORIZED,
+            ApiError::Forbidden { .. } => StatusCode::FORBIDDEN,
+            ApiError::ValidationError(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::Unknown { .. } => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
     fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code())
+            .content_type(PROBLEM_CONTENT_TYPE)
+            .json(self.problem())
+    }
+}
+
+impl ErrorCode for TranslationRecordError {
+    fn code(&self) -> &'static str {
+        match self {
+            TranslationRecordError::EmptyWord => "empty_word",
+            TranslationRecordError::EmptyTranslation => "empty_translation",
+            TranslationRecordError::EmptyWordInTranslation => "empty_word_in_translation",
+            TranslationRecordError::TranslationLanguageMismatch => "translation_language_mismatch",
+            TranslationRecordError::UpdateWithSameItems => "update_with_same_items",
+            TranslationRecordError::UnsupportedLang(_) => "unsupported_lang",
+            TranslationRecordError::Blank => "blank",
+            TranslationRecordError::TooLong => "too_long",
+            TranslationRecordError::IllegalCharacter(_) => "illegal_character",
+            TranslationRecordError::AmbiguousSense => "ambiguous_sense",
+            TranslationRecordError::UnknownSense(_) => "unknown_sense",
+        }
+    }
+}
+
+impl ErrorCode for RepoCreateError {
+    fn code(&self) -> &'static str {
+        match self {
+            RepoCreateError::Duplicate => "duplicate",
+            RepoCreateError::Unknown => "repository_create_failed",
+        }
+    }
+}
+
+impl ErrorCode for RepoReadError {
+    fn code(&self) -> &'static str {
+        match self {
+            RepoReadError::NotFound => "not_found",
+            RepoReadError::Unknown => "repository_read_failed",
+        }
+    }
+}
+
+impl ErrorCode for RepoUpdateError {
+    fn code(&self) -> &'static str {
+        match self {
+            RepoUpdateError::BadId => "bad_id",
+            RepoUpdateError::NotFound => "not_found",
+            RepoUpdateError::Unknown => "repository_update_failed",
+        }
+    }
+}
+
+impl ErrorCode for RepoDeleteError {
+    fn code(&self) -> &'static str {
+        match self {
+            RepoDeleteError::BadId => "bad_id",
+            RepoDeleteError::NotFound => "not_found",
+            RepoDeleteError::Unknown => "repository_delete_failed",
+        }
+    }
+}
+
+impl ErrorCode for CreateError {
+    fn code(&self) -> &'static str {
+        match self {
+            CreateError::InvalidInput(e) => e.code(),
+            CreateError::Read(e) => e.code(),
+            CreateError::Create(e) => e.code(),
+            CreateError::Duplicate => "duplicate",
+            CreateError::ReadOnly => "read_only",
+        }
+    }
+}
+
+impl ErrorCode for ReadError {
+    fn code(&self) -> &'static str {
         match self {
-            ApiError::BadRequest(s) => HttpResponse::BadRequest().json(s),
-            ApiError::NotFound(s) => HttpResponse::NotFound().json(s),
-            ApiError::InvalidInput(s) => HttpResponse::BadRequest().json(s),
-            ApiError::Conflict(s) => HttpResponse::Conflict().json(s),
-            ApiError::ValidationError(s) => HttpResponse::UnprocessableEntity().json(s.to_vec()),
-            ApiError::Unknown(_) => HttpResponse::new(StatusCode::INTERNAL_SERVER_ERROR),
+            ReadError::QueryWord(e) => e.code(),
+            ReadError::RecordNotFound(_) => "not_found",
+            ReadError::Unknown => "unknown",
         }
     }
 }
+
+impl ErrorCode for UpdateError {
+    fn code(&self) -> &'static str {
+        match self {
+            UpdateError::Word(e) => e.code(),
+            UpdateError::Read(e) => e.code(),
+            UpdateError::Update(e) => e.code(),
+            UpdateError::ReadOnly => "read_only",
+        }
+    }
+}
+
+impl ErrorCode for DeleteError {
+    fn code(&self) -> &'static str {
+        match self {
+            DeleteError::Word(e) => e.code(),
+            DeleteError::Read(e) => e.code(),
+            DeleteError::Delete(e) => e.code(),
+            DeleteError::ReadOnly => "read_only",
+        }
+    }
+}
+
+impl ErrorCode for ListError {
+    fn code(&self) -> &'static str {
+        match self {
+            ListError::Unknown => "unknown",
+        }
+    }
+}
+
+impl ErrorCode for SearchError {
+    fn code(&self) -> &'static str {
+        match self {
+            SearchError::EmptyQuery => "empty_query",
+            SearchError::Unknown => "unknown",
+        }
+    }
+}
+
+impl ErrorCode for QuizError {
+    fn code(&self) -> &'static str {
+        match self {
+            QuizError::Word(e) => e.code(),
+            QuizError::RecordNotFound => "not_found",
+            QuizError::Empty => "nothing_to_practice",
+            QuizError::NotConjugated => "not_conjugated",
+            QuizError::Unknown => "unknown",
+        }
+    }
+}
+
+impl ErrorCode for AuthError {
+    fn code(&self) -> &'static str {
+        match self {
+            AuthError::MissingKey => "missing_api_key",
+            AuthError::UnknownKey => "unknown_api_key",
+            AuthError::MissingScope(..) => "insufficient_scope",
+        }
+    }
+}
+
+impl ErrorCode for AccountError {
+    fn code(&self) -> &'static str {
+        match self {
+            AccountError::InvalidName => "invalid_name",
+            AccountError::WeakPassword => "weak_password",
+            AccountError::NameTaken => "name_taken",
+            AccountError::InvalidCredentials => "invalid_credentials",
+            AccountError::Read(e) => e.code(),
+            AccountError::Create(e) => e.code(),
+            AccountError::Hash(_) => "password_hash_failed",
+        }
+    }
+}
+
+impl ErrorCode for SessionError {
+    fn code(&self) -> &'static str {
+        match self {
+            SessionError::MissingToken => "missing_token",
+            SessionError::InvalidToken => "invalid_token",
+            SessionError::Issue(_) => "token_issue_failed",
+        }
+    }
+}
+
+impl ErrorCode for DeckError {
+    fn code(&self) -> &'static str {
+        match self {
+            DeckError::InvalidName => "invalid_deck_name",
+            DeckError::LoginRequired => "login_required",
+            DeckError::NotFound => "deck_not_found",
+            DeckError::NotOwner => "not_deck_owner",
+            DeckError::NotSubscribed => "not_subscribed",
+            DeckError::UnknownUser(_) => "unknown_user",
+            DeckError::Read(e) => e.code(),
+            DeckError::Create(e) => e.code(),
+            DeckError::Update(e) => e.code(),
+        }
+    }
+}
+
+impl ErrorCode for LinkError {
+    fn code(&self) -> &'static str {
+        match self {
+            LinkError::SelfLink => "self_link",
+            LinkError::NotFound => "not_found",
+            LinkError::ReadOnly => "read_only",
+            LinkError::Read(e) => e.code(),
+            LinkError::Create(e) => e.code(),
+        }
+    }
+}
+
+impl ErrorCode for ConjugationError {
+    fn code(&self) -> &'static str {
+        match self {
+            ConjugationError::NotRegular(_) => "not_regular",
+            ConjugationError::NoTenses => "no_tenses",
+            ConjugationError::FormCount(_) => "form_count",
+            ConjugationError::DuplicateTense(_) => "duplicate_tense",
+            ConjugationError::MissingTense(_) => "missing_tense",
+            ConjugationError::Word(e) => e.code(),
+            ConjugationError::Read(e) => e.code(),
+            ConjugationError::Update(e) => e.code(),
+            ConjugationError::ReadOnly => "read_only",
+        }
+    }
+}
+
+impl ErrorCode for RevisionError {
+    fn code(&self) -> &'static str {
+        match self {
+            RevisionError::NotFound => "not_found",
+            RevisionError::UnknownRevision => "unknown_revision",
+            RevisionError::Deletion => "revision_deleted",
+            RevisionError::ReadOnly => "read_only",
+            RevisionError::Read(e) => e.code(),
+            RevisionError::Update(e) => e.code(),
+        }
+    }
+}
+
+impl ErrorCode for TrashError {
+    fn code(&self) -> &'static str {
+        match self {
+            TrashError::NotFound => "not_in_trash",
+            TrashError::Duplicate => "duplicate",
+            TrashError::ReadOnly => "read_only",
+            TrashError::Read(e) => e.code(),
+            TrashError::Update(e) => e.code(),
+            TrashError::Delete(e) => e.code(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::body::to_bytes;
+
+    use super::*;
+
+    #[test]
+    fn wrapped_errors_carry_inner_code() {
+        let e = CreateError::InvalidInput(TranslationRecordError::EmptyWord);
+
+        assert_eq!(e.code(), "empty_word");
+        assert_eq!(CreateError::Duplicate.code(), "duplicate");
+    }
+
+    #[actix_rt::test]
+    async fn error_response_is_problem_json() {
+        let response = ApiError::conflict(&CreateError::Duplicate).error_response();
+
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        assert_eq!(
+            response.headers().get("content-type").unwrap(),
+            PROBLEM_CONTENT_TYPE
+        );
+
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let problem: Problem = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem, Problem::new(409, "duplicate", "Duplicate"));
+    }
+
+    #[test]
+    fn validation_problem_keyed_by_field() {
+        let fields = BTreeMap::from([("word".to_string(), vec!["Word is required".to_string()])]);
+
+        let problem = ApiError::ValidationError(fields.clone()).problem();
+
+        assert_eq!(problem.code, VALIDATION_CODE);
+        assert_eq!(problem.status, 422);
+        assert_eq!(problem.errors, Some(fields));
+    }
+
+    #[test]
+    fn not_found_problem_carries_suggestions() {
+        let e = ReadError::RecordNotFound(vec!["chien".to_string()]);
+
+        let with = ApiError::not_found_with_suggestions(&e, &["chien".to_string()]).problem();
+        let without = ApiError::not_found(&e).problem();
+
+        assert_eq!(with.status, 404);
+        assert_eq!(with.suggestions, Some(vec!["chien".to_string()]));
+        assert_eq!(without.suggestions, None);
+    }
+}
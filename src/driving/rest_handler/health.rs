@@ -0,0 +1,98 @@
+use actix_web::{HttpResponse, web};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::domain;
+use crate::domain::health::HealthError;
+use crate::domain::ports::TranslationRepository;
+
+const UP: &str = "up";
+const DOWN: &str = "down";
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, ToSchema)]
+pub struct HealthCheck {
+    pub name: String,
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, ToSchema)]
+pub struct HealthResponse {
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<HealthCheck>,
+}
+
+/// The process is up
+#[utoipa::path(
+    get,
+    path = "/voci/health/live",
+    tag = "health",
+    responses((status = 200, description = "Process is up", body = HealthResponse))
+)]
+pub async fn live() -> HttpResponse {
+    HttpResponse::Ok().json(HealthResponse {
+        status: UP.to_string(),
+        checks: vec![],
+    })
+}
+
+/// All dependencies needed to serve requests are available
+#[utoipa::path(
+    get,
+    path = "/voci/health/ready",
+    tag = "health",
+    responses(
+        (status = 200, description = "Ready to serve requests", body = HealthResponse),
+        (status = 503, description = "A dependency is unavailable", body = HealthResponse),
+    )
+)]
+pub async fn ready<T: TranslationRepository>(repository: web::Data<T>) -> HttpResponse {
+    match domain::health::check_readiness(repository.get_ref()).await {
+        Ok(()) => HttpResponse::Ok().json(HealthResponse {
+            status: UP.to_string(),
+            checks: vec![HealthCheck {
+                name: "repository".to_string(),
+                status: UP.to_string(),
+                detail: None,
+            }],
+        }),
+        Err(HealthError::Repository(e)) => {
+            HttpResponse::ServiceUnavailable().json(HealthResponse {
+                status: DOWN.to_string(),
+                checks: vec![HealthCheck {
+                    name: "repository".to_string(),
+                    status: DOWN.to_string(),
+                    detail: Some(e.to_string()),
+                }],
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::body::to_bytes;
+    use actix_web::http::StatusCode;
+
+    use super::*;
+    use crate::test_utils::{utils::shared::*, voci_repo_double::repo_double::VociRepoDouble};
+
+    #[actix_rt::test]
+    async fn ready_repository_down_service_unavailable() {
+        let mut repo = VociRepoDouble::new(&get_testing_persistence_config()).unwrap();
+        repo.set_error(true);
+
+        let response = ready(web::Data::new(repo)).await;
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let health: HealthResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(health.status, DOWN);
+        assert_eq!(
+            health.checks[0].detail.as_deref(),
+            Some("Unavailable: provoked")
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::middleware::Next;
+use actix_web::{Error, HttpResponse, web};
+use metrics_exporter_prometheus::PrometheusHandle;
+use std::time::Instant;
+
+use crate::telemetry;
+
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+/// Route label of requests not matching any resource, keeps the label cardinality bounded
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// Counts requests and records their latency per method, route pattern and status
+pub async fn track_requests(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let start = Instant::now();
+    let method = req.method().to_string();
+
+    let result = next.call(req).await;
+
+    let (route, status) = match &result {
+        Ok(res) => (res.request().match_pattern(), res.status().as_u16()),
+        Err(e) => (None, e.as_response_error().status_code().as_u16()),
+    };
+    let route = route.unwrap_or_else(|| UNMATCHED_ROUTE.to_string());
+    telemetry::record_request(&method, &route, status, start.elapsed());
+
+    result
+}
+
+/// Scrape endpoint in Prometheus text format
+pub async fn metrics(handle: web::Data<PrometheusHandle>) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type(PROMETHEUS_CONTENT_TYPE)
+        .body(handle.render())
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::middleware::from_fn;
+    use actix_web::{App, test};
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn requests_tracked_by_route_pattern() {
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(track_requests))
+                .app_data(web::Data::new(telemetry::install()))
+                .route("/probe/{id}", web::get().to(HttpResponse::Ok))
+                .route("/metrics", web::get().to(metrics)),
+        )
+        .await;
+
+        test::call_service(&app, test::TestRequest::get().uri("/probe/42").to_request()).await;
+        let response =
+            test::call_service(&app, test::TestRequest::get().uri("/metrics").to_request()).await;
+
+        let body = test::read_body(response).await;
+        let rendered = String::from_utf8(body.to_vec()).unwrap();
+        assert!(rendered.contains(
+            r#"voci_http_requests_total{method="GET",route="/probe/{id}",status="200"}"#
+        ));
+    }
+}
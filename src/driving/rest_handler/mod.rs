@@ -1,4 +1,13 @@
+pub mod accounts;
+pub mod auth;
+pub mod decks;
 pub mod errors;
+pub mod health;
+pub mod metrics;
+pub mod openapi;
+pub mod quiz;
+pub mod request_id;
+pub mod session;
 pub mod vocis;
 
 mod validate;
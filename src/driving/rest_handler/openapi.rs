@@ -0,0 +1,204 @@
+use actix_web::HttpResponse;
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+
+use crate::domain::conjugation::{Conjugation, Form, Person, Tense, TenseForms};
+use crate::domain::link::Relation;
+use crate::domain::quiz::Progress;
+use crate::domain::revision::Change;
+use crate::domain::voci::{Gender, Lang};
+use crate::driving::rest_handler::accounts::{
+    self, CredentialsRequest, TokenResponse, UserResponse,
+};
+use crate::driving::rest_handler::decks::{self, CreateDeckRequest, DeckResponse, EditorRequest};
+use crate::driving::rest_handler::errors::Problem;
+use crate::driving::rest_handler::health::{self, HealthCheck, HealthResponse};
+use crate::driving::rest_handler::quiz::{
+    self, ConjugationAnswerRequest, ConjugationAnswerResponse, ConjugationQuestionResponse,
+    QuizAnswerRequest, QuizAnswerResponse, QuizQuestion,
+};
+use crate::driving::rest_handler::vocis::{
+    self, ConjugationRequest, CreateTranslationRequest, LinkRequest, LinkResponse, LinkedResponse,
+    RequestTranslationByWord, RevertRequest, RevisionResponse, TranslationResponse,
+};
+
+/// OpenAPI document generated from the handlers and their DTOs
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Vocabulaire",
+        description = "Practice vocabulary from and to translations"
+    ),
+    paths(
+        vocis::create_translation,
+        vocis::read_translation,
+        vocis::update_translation,
+        vocis::delete_translation,
+        vocis::search_translations,
+        vocis::link_translations,
+        vocis::conjugate_translation,
+        vocis::list_trash,
+        vocis::restore_translation,
+        vocis::translation_history,
+        vocis::revert_translation,
+        quiz::draw_quiz,
+        quiz::answer_quiz,
+        quiz::draw_conjugation,
+        quiz::answer_conjugation,
+        accounts::register,
+        accounts::login,
+        decks::create_deck,
+        decks::add_editor,
+        decks::subscribe,
+        decks::unsubscribe,
+        health::live,
+        health::ready,
+    ),
+    components(schemas(
+        Lang,
+        Gender,
+        Problem,
+        CreateTranslationRequest,
+        RequestTranslationByWord,
+        TranslationResponse,
+        Relation,
+        LinkedResponse,
+        LinkRequest,
+        LinkResponse,
+        Change,
+        RevisionResponse,
+        RevertRequest,
+        Tense,
+        Person,
+        TenseForms,
+        Form,
+        Conjugation,
+        ConjugationRequest,
+        QuizQuestion,
+        QuizAnswerRequest,
+        QuizAnswerResponse,
+        ConjugationQuestionResponse,
+        ConjugationAnswerRequest,
+        ConjugationAnswerResponse,
+        Progress,
+        CredentialsRequest,
+        UserResponse,
+        TokenResponse,
+        CreateDeckRequest,
+        EditorRequest,
+        DeckResponse,
+        HealthCheck,
+        HealthResponse,
+    )),
+    modifiers(&SecurityAddon)
+)]
+pub struct ApiDoc;
+
+/// Declares the `X-Api-Key` header referenced by the protected paths and the login token
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "api_key",
+                SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-Api-Key"))),
+            );
+            components.add_security_scheme(
+                "bearer",
+                SecurityScheme::Http(
+                    HttpBuilder::new()
+                        .scheme(HttpAuthScheme::Bearer)
+                        .bearer_format("JWT")
+                        .build(),
+                ),
+            );
+        }
+    }
+}
+
+/// Page rendering the OpenAPI document with Redoc
+const DOCS_PAGE: &str = r#"<!DOCTYPE html>
+<html>
+  <head>
+    <title>Vocabulaire API</title>
+    <meta charset="utf-8"/>
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+  </head>
+  <body>
+    <redoc spec-url="/voci/api/v1/openapi.json"></redoc>
+    <script src="/voci/api/v1/docs/redoc.standalone.js"></script>
+  </body>
+</html>
+"#;
+
+/// Redoc 2.0.0 bundle, served from here rather than a CDN
+const REDOC_JS: &str = include_str!("../../../resources/redoc/redoc.standalone.js");
+
+pub async fn openapi_json() -> HttpResponse {
+    HttpResponse::Ok().json(ApiDoc::openapi())
+}
+
+pub async fn docs() -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(DOCS_PAGE)
+}
+
+pub async fn redoc_js() -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/javascript; charset=utf-8")
+        .insert_header(("Cache-Control", "public, max-age=86400"))
+        .body(REDOC_JS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn openapi_contains_all_routes() {
+        let doc = ApiDoc::openapi();
+
+        let paths: Vec<&String> = doc.paths.paths.keys().collect();
+        assert_eq!(
+            paths,
+            vec![
+                "/voci/api/v1/decks",
+                "/voci/api/v1/decks/{id}/editors",
+                "/voci/api/v1/decks/{id}/subscription",
+                "/voci/api/v1/login",
+                "/voci/api/v1/quiz",
+                "/voci/api/v1/quiz/answer",
+                "/voci/api/v1/quiz/conjugation",
+                "/voci/api/v1/translations",
+                "/voci/api/v1/translations/conjugation",
+                "/voci/api/v1/translations/search",
+                "/voci/api/v1/translations/trash",
+                "/voci/api/v1/translations/{id}/history",
+                "/voci/api/v1/translations/{id}/links",
+                "/voci/api/v1/translations/{id}/restore",
+                "/voci/api/v1/translations/{id}/revert",
+                "/voci/api/v1/users",
+                "/voci/health/live",
+                "/voci/health/ready",
+            ]
+        );
+    }
+
+    #[test]
+    fn openapi_carries_validation_constraints() {
+        let json = serde_json::to_value(ApiDoc::openapi()).unwrap();
+
+        let word = &json["components"]["schemas"]["CreateTranslationRequest"]["properties"]["word"];
+        assert_eq!(word["minLength"], 1);
+    }
+
+    #[test]
+    fn docs_page_loads_no_third_party_script() {
+        assert!(DOCS_PAGE.contains(r#"<script src="/voci/api/v1/docs/redoc.standalone.js">"#));
+        assert!(!DOCS_PAGE.contains("https://"));
+        assert!(REDOC_JS.contains(r#"const rw="2.0.0""#));
+        assert!(!REDOC_JS.contains("sourceMappingURL=redoc.standalone.js.map"));
+    }
+}
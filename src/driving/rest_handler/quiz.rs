@@ -0,0 +1,341 @@
+use actix_web::web;
+use actix_web::web::{Json, Query};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+use crate::domain;
+use crate::domain::conjugation::{Person, Tense};
+use crate::domain::ports::{
+    DeckRepository, LinkRepository, ProgressRepository, TranslationRepository,
+};
+use crate::domain::quiz::{
+    ConjugationGrade, ConjugationQuestion, Grade, Progress, Question, QuizError,
+};
+use crate::domain::voci::Lang;
+
+use crate::driving::rest_handler::decks::{DeckParam, resolve_access};
+use crate::driving::rest_handler::errors::{ApiError, Problem};
+use crate::driving::rest_handler::session::Caller;
+use crate::driving::rest_handler::validate::{validate, validate_word};
+
+#[derive(Clone, Debug, Deserialize, Serialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct QuizRequest {
+    /// Number of words to practice
+    #[validate(range(min = 1, max = 100, message = "size must be between 1 and 100"))]
+    #[param(minimum = 1, maximum = 100)]
+    pub size: usize,
+}
+
+/// A word to be translated, without giving away the translations
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, ToSchema)]
+pub struct QuizQuestion {
+    pub word: String,
+    pub lang: Lang,
+    pub translation_lang: Lang,
+    /// Wrong answers taken from antonyms and the word family, to choose from
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub distractors: Vec<String>,
+}
+impl From<Question> for QuizQuestion {
+    fn from(q: Question) -> Self {
+        let (_, word, lang, _, translation_lang) = q.record.flat();
+        QuizQuestion {
+            word: word.clone(),
+            lang: lang.clone(),
+            translation_lang: translation_lang.clone(),
+            distractors: q.distractors,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Validate, ToSchema)]
+pub struct QuizAnswerRequest {
+    #[validate(
+        length(min = 1, message = "Word is required"),
+        custom(function = "validate_word")
+    )]
+    #[schema(min_length = 1)]
+    pub word: String,
+    pub lang: Lang,
+    pub answer: String,
+    /// Whether the answer must carry the article of the translation, e.g. `der Hund`
+    #[serde(default)]
+    pub require_article: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, ToSchema)]
+pub struct QuizAnswerResponse {
+    pub correct: bool,
+    pub translations: Vec<String>,
+    /// Learning progress of the caller on the word, including this answer
+    pub progress: Progress,
+}
+impl From<Grade> for QuizAnswerResponse {
+    fn from(g: Grade) -> Self {
+        QuizAnswerResponse {
+            correct: g.correct,
+            translations: g.translations,
+            progress: g.progress,
+        }
+    }
+}
+
+/// A verb form to give, e.g. "aller, présent, nous"
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, ToSchema)]
+pub struct ConjugationQuestionResponse {
+    pub word: String,
+    #[serde(default)]
+    pub sense: Option<String>,
+    pub tense: Tense,
+    pub person: Person,
+    /// The question as shown to learners
+    pub prompt: String,
+}
+impl From<ConjugationQuestion> for ConjugationQuestionResponse {
+    fn from(q: ConjugationQuestion) -> Self {
+        ConjugationQuestionResponse {
+            prompt: q.prompt(),
+            word: q.word,
+            sense: q.sense,
+            tense: q.tense,
+            person: q.person,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Validate, ToSchema)]
+pub struct ConjugationAnswerRequest {
+    #[validate(
+        length(min = 1, message = "Word is required"),
+        custom(function = "validate_word")
+    )]
+    #[schema(min_length = 1)]
+    pub word: String,
+    /// Sense of a verb with several meanings
+    #[serde(default)]
+    #[validate(length(max = 100, message = "Sense must be at most 100 characters"))]
+    #[schema(max_length = 100)]
+    pub sense: Option<String>,
+    pub tense: Tense,
+    pub person: Person,
+    /// The verb form, with or without subject pronoun
+    pub answer: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, ToSchema)]
+pub struct ConjugationAnswerResponse {
+    pub correct: bool,
+    pub expected: String,
+}
+impl From<ConjugationGrade> for ConjugationAnswerResponse {
+    fn from(g: ConjugationGrade) -> Self {
+        ConjugationAnswerResponse {
+            correct: g.correct,
+            expected: g.expected,
+        }
+    }
+}
+
+fn map_quiz_error(e: QuizError) -> ApiError {
+    match e {
+        QuizError::Word(_) => ApiError::invalid_input(&e),
+        QuizError::RecordNotFound | QuizError::Empty | QuizError::NotConjugated => {
+            ApiError::not_found(&e)
+        }
+        QuizError::Unknown => ApiError::unknown(&e),
+    }
+}
+
+/// Draw a randomized batch of words to practice
+#[utoipa::path(
+    get,
+    path = "/voci/api/v1/quiz",
+    tag = "quiz",
+    params(QuizRequest, DeckParam),
+    responses(
+        (status = 200, description = "Words to translate", body = Vec<QuizQuestion>),
+        (
+            status = 404, description = "Nothing to practice",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 422, description = "Validation failed",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 500, description = "Unknown error",
+            body = Problem, content_type = "application/problem+json"
+        ),
+    )
+)]
+#[instrument(skip_all, fields(size = request.size))]
+pub async fn draw_quiz<T: TranslationRepository + DeckRepository + LinkRepository>(
+    repository: web::Data<T>,
+    caller: Caller,
+    request: Query<QuizRequest>,
+) -> Result<Json<Vec<QuizQuestion>>, ApiError> {
+    validate(&request)?;
+    let access = resolve_access(repository.get_ref(), &caller).await?;
+
+    let result = domain::quiz::draw_quiz(repository.get_ref(), &access, request.size).await;
+
+    result
+        .map(|v| Json(v.into_iter().map(QuizQuestion::from).collect()))
+        .map_err(map_quiz_error)
+}
+
+/// Grade the answer given for a word
+#[utoipa::path(
+    post,
+    path = "/voci/api/v1/quiz/answer",
+    tag = "quiz",
+    params(DeckParam),
+    request_body = QuizAnswerRequest,
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Graded answer", body = QuizAnswerResponse),
+        (
+            status = 400, description = "Invalid input",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "API key missing or unknown",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "API key lacks the write scope",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Translation not found",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 422, description = "Validation failed",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 500, description = "Unknown error",
+            body = Problem, content_type = "application/problem+json"
+        ),
+    )
+)]
+#[instrument(skip_all, fields(word = %request.word, lang = %request.lang))]
+pub async fn answer_quiz<T: TranslationRepository + DeckRepository + ProgressRepository>(
+    repository: web::Data<T>,
+    caller: Caller,
+    request: Json<QuizAnswerRequest>,
+) -> Result<Json<QuizAnswerResponse>, ApiError> {
+    validate(&request)?;
+    let access = resolve_access(repository.get_ref(), &caller).await?;
+
+    let result = domain::quiz::check_answer(
+        repository.get_ref(),
+        &access,
+        &request.word,
+        &request.lang,
+        &request.answer,
+        request.require_article,
+    )
+    .await;
+
+    result
+        .map(|v| Json(QuizAnswerResponse::from(v)))
+        .map_err(map_quiz_error)
+}
+
+/// Draw a form of a conjugated French verb to practice
+#[utoipa::path(
+    get,
+    path = "/voci/api/v1/quiz/conjugation",
+    tag = "quiz",
+    params(DeckParam),
+    responses(
+        (status = 200, description = "Verb form to give", body = ConjugationQuestionResponse),
+        (
+            status = 404, description = "No conjugated verb to practice",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 500, description = "Unknown error",
+            body = Problem, content_type = "application/problem+json"
+        ),
+    )
+)]
+#[instrument(skip_all)]
+pub async fn draw_conjugation<T: TranslationRepository + DeckRepository>(
+    repository: web::Data<T>,
+    caller: Caller,
+) -> Result<Json<ConjugationQuestionResponse>, ApiError> {
+    let access = resolve_access(repository.get_ref(), &caller).await?;
+
+    let result = domain::quiz::draw_conjugation(repository.get_ref(), &access).await;
+
+    result
+        .map(|q| Json(ConjugationQuestionResponse::from(q)))
+        .map_err(map_quiz_error)
+}
+
+/// Grade the form given for a verb
+#[utoipa::path(
+    post,
+    path = "/voci/api/v1/quiz/conjugation",
+    tag = "quiz",
+    params(DeckParam),
+    request_body = ConjugationAnswerRequest,
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Graded answer", body = ConjugationAnswerResponse),
+        (
+            status = 400, description = "Invalid input or sense needed",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "API key missing or unknown",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "API key lacks the write scope",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Verb or its conjugation in the tense not found",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 422, description = "Validation failed",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 500, description = "Unknown error",
+            body = Problem, content_type = "application/problem+json"
+        ),
+    )
+)]
+#[instrument(skip_all, fields(word = %request.word, tense = %request.tense, person = %request.person))]
+pub async fn answer_conjugation<T: TranslationRepository + DeckRepository>(
+    repository: web::Data<T>,
+    caller: Caller,
+    request: Json<ConjugationAnswerRequest>,
+) -> Result<Json<ConjugationAnswerResponse>, ApiError> {
+    validate(&request)?;
+    let access = resolve_access(repository.get_ref(), &caller).await?;
+
+    let result = domain::quiz::check_conjugation(
+        repository.get_ref(),
+        &access,
+        &request.word,
+        request.sense.as_deref(),
+        request.tense,
+        request.person,
+        &request.answer,
+    )
+    .await;
+
+    result
+        .map(|g| Json(ConjugationAnswerResponse::from(g)))
+        .map_err(map_quiz_error)
+}
@@ -0,0 +1,96 @@
+use actix_web::Error;
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{HeaderName, HeaderValue};
+use actix_web::middleware::Next;
+use tracing::{Instrument, info_span};
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+/// Longer IDs sent by clients are replaced, they end up in every log line of the request
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Runs the request inside a span carrying its request ID and echoes the ID
+///
+/// The ID is taken from the `X-Request-Id` header if the client sent a sane one,
+/// else a new one is generated.
+pub async fn trace_request(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let request_id = req
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|id| id.to_str().ok())
+        .filter(|id| is_valid(id))
+        .map(str::to_string)
+        .unwrap_or_else(generate);
+
+    let span = info_span!(
+        "request",
+        request_id = %request_id,
+        method = %req.method(),
+        path = %req.path(),
+    );
+
+    let mut res = next.call(req).instrument(span).await?;
+
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        res.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    Ok(res)
+}
+
+fn is_valid(id: &str) -> bool {
+    !id.is_empty() && id.len() <= MAX_REQUEST_ID_LEN && id.bytes().all(|b| b.is_ascii_graphic())
+}
+
+fn generate() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::middleware::from_fn;
+    use actix_web::{App, HttpResponse, test, web};
+
+    use super::*;
+
+    async fn request_id_of(request: test::TestRequest) -> String {
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(trace_request))
+                .route("/", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let response = test::call_service(&app, request.to_request()).await;
+
+        response
+            .headers()
+            .get(&REQUEST_ID_HEADER)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[actix_rt::test]
+    async fn request_id_echoed() {
+        let request = test::TestRequest::get().insert_header((REQUEST_ID_HEADER, "abc-123"));
+
+        assert_eq!(request_id_of(request).await, "abc-123");
+    }
+
+    #[actix_rt::test]
+    async fn request_id_generated_if_missing_or_invalid() {
+        let missing = request_id_of(test::TestRequest::get()).await;
+        let too_long = request_id_of(
+            test::TestRequest::get().insert_header((REQUEST_ID_HEADER, "x".repeat(200))),
+        )
+        .await;
+
+        assert_eq!(missing.len(), 32);
+        assert_eq!(too_long.len(), 32);
+        assert_ne!(missing, too_long);
+    }
+}
@@ -0,0 +1,214 @@
+use actix_web::dev::Payload;
+use actix_web::http::header::AUTHORIZATION;
+use actix_web::{FromRequest, HttpRequest, web};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation, decode, encode};
+use serde::{Deserialize, Serialize};
+use std::future::{Ready, ready};
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+use crate::config::AuthConfig;
+use crate::domain::deck::DeckId;
+use crate::domain::user::{User, UserId};
+use crate::driving::rest_handler::decks::DeckParam;
+use crate::driving::rest_handler::errors::ApiError;
+
+const BEARER_PREFIX: &str = "Bearer ";
+
+#[derive(Debug, PartialEq, Error)]
+pub enum SessionError {
+    #[error("Login token required")]
+    MissingToken,
+    #[error("Login token invalid or expired")]
+    InvalidToken,
+    #[error("Cannot issue login token: {0}")]
+    Issue(String),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Claims {
+    /// ID of the user
+    sub: String,
+    name: String,
+    iat: u64,
+    exp: u64,
+}
+
+/// Issues and verifies the HS256 signed login tokens
+#[derive(Clone)]
+pub struct TokenIssuer {
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    ttl: u64,
+}
+
+impl TokenIssuer {
+    pub fn new(secret: &[u8], ttl: u64) -> Self {
+        TokenIssuer {
+            encoding: EncodingKey::from_secret(secret),
+            decoding: DecodingKey::from_secret(secret),
+            ttl,
+        }
+    }
+
+    /// Lifetime of issued tokens in seconds
+    pub fn ttl(&self) -> u64 {
+        self.ttl
+    }
+
+    pub fn issue(&self, user: &User) -> Result<String, SessionError> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| SessionError::Issue(e.to_string()))?
+            .as_secs();
+        let claims = Claims {
+            sub: user.id().value().to_string(),
+            name: user.name().to_string(),
+            iat: now,
+            exp: now + self.ttl,
+        };
+
+        encode(&Header::new(Algorithm::HS256), &claims, &self.encoding)
+            .map_err(|e| SessionError::Issue(e.to_string()))
+    }
+
+    pub fn verify(&self, token: &str) -> Result<UserId, SessionError> {
+        decode::<Claims>(token, &self.decoding, &Validation::new(Algorithm::HS256))
+            .map(|data| UserId::new(&data.claims.sub))
+            .map_err(|_| SessionError::InvalidToken)
+    }
+}
+
+/// The user a request acts for, taken from the `Authorization: Bearer` token
+///
+/// Requests without token act for the anonymous user, unless `require_login` is configured.
+/// The `deck` query parameter selects a deck to work on instead of the user's own vocabulary.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Caller {
+    pub user: UserId,
+    pub deck: Option<DeckId>,
+}
+
+impl FromRequest for Caller {
+    type Error = ApiError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(caller(req))
+    }
+}
+
+fn caller(req: &HttpRequest) -> Result<Caller, ApiError> {
+    let token = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix(BEARER_PREFIX));
+    let require_login = req
+        .app_data::<web::Data<AuthConfig>>()
+        .is_some_and(|auth| auth.require_login);
+
+    let user = match (token, req.app_data::<web::Data<TokenIssuer>>()) {
+        (Some(token), Some(issuer)) => issuer
+            .verify(token)
+            .map_err(|e| ApiError::unauthorized(&e))?,
+        (Some(_), None) => return Err(ApiError::unauthorized(&SessionError::InvalidToken)),
+        (None, _) if require_login => {
+            return Err(ApiError::unauthorized(&SessionError::MissingToken));
+        }
+        (None, _) => UserId::anonymous(),
+    };
+    let deck = web::Query::<DeckParam>::from_query(req.query_string())
+        .ok()
+        .and_then(|q| q.into_inner().deck)
+        .map(|id| DeckId::new(&id));
+
+    Ok(Caller { user, deck })
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::test::TestRequest;
+
+    use super::*;
+
+    const SECRET: &[u8] = b"0123456789abcdef0123456789abcdef";
+
+    fn request(issuer: TokenIssuer, auth: AuthConfig, token: Option<&str>) -> HttpRequest {
+        let mut request = TestRequest::get()
+            .app_data(web::Data::new(issuer))
+            .app_data(web::Data::new(auth));
+        if let Some(token) = token {
+            request = request.insert_header((AUTHORIZATION, format!("Bearer {token}")));
+        }
+        request.to_http_request()
+    }
+
+    #[test]
+    fn issued_token_verified() {
+        let issuer = TokenIssuer::new(SECRET, 60);
+        let user = User::new("user-1", "alice", "hash");
+
+        let token = issuer.issue(&user).unwrap();
+
+        assert_eq!(issuer.verify(&token), Ok(UserId::new("user-1")));
+        assert_eq!(
+            TokenIssuer::new(b"another secret", 60).verify(&token),
+            Err(SessionError::InvalidToken)
+        );
+    }
+
+    #[test]
+    fn caller_from_token_or_anonymous() {
+        let issuer = TokenIssuer::new(SECRET, 60);
+        let token = issuer.issue(&User::new("user-1", "alice", "hash")).unwrap();
+
+        let logged_in = request(issuer.clone(), AuthConfig::default(), Some(&token));
+        let anonymous = request(issuer, AuthConfig::default(), None);
+
+        assert_eq!(
+            caller(&logged_in).map(|c| c.user),
+            Ok(UserId::new("user-1"))
+        );
+        assert_eq!(
+            caller(&anonymous),
+            Ok(Caller {
+                user: UserId::anonymous(),
+                deck: None
+            })
+        );
+    }
+
+    #[test]
+    fn caller_deck_from_query() {
+        let request = TestRequest::get()
+            .uri("/voci/api/v1/quiz?size=5&deck=abc")
+            .to_http_request();
+
+        assert_eq!(
+            caller(&request).map(|c| c.deck),
+            Ok(Some(DeckId::new("abc")))
+        );
+    }
+
+    #[test]
+    fn caller_bad_or_required_token_unauthorized() {
+        let issuer = TokenIssuer::new(SECRET, 60);
+        let required = AuthConfig {
+            require_login: true,
+            ..AuthConfig::default()
+        };
+
+        let forged = request(issuer.clone(), AuthConfig::default(), Some("forged"));
+        let missing = request(issuer, required, None);
+
+        assert_eq!(
+            caller(&forged),
+            Err(ApiError::unauthorized(&SessionError::InvalidToken))
+        );
+        assert_eq!(
+            caller(&missing),
+            Err(ApiError::unauthorized(&SessionError::MissingToken))
+        );
+    }
+}
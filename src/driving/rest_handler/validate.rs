@@ -1,10 +1,15 @@
-use actix_web::web::Json;
-use validator::{Validate, ValidationErrors};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::ops::Deref;
+use validator::{Validate, ValidationError, ValidationErrors};
 
+use crate::domain::text;
 use crate::driving::rest_handler::errors::ApiError;
 
-pub fn validate<T>(params: &Json<T>) -> Result<(), ApiError>
+/// Validates the content of an extractor such as `Json` or `Query`
+pub fn validate<T, P>(params: &P) -> Result<(), ApiError>
 where
+    P: Deref<Target = T>,
     T: Validate,
 {
     match params.validate() {
@@ -13,17 +18,104 @@ where
     }
 }
 
-fn collect_errors(error: ValidationErrors) -> Vec<String> {
+/// Rejects words that are blank, too long or hold control or invisible characters, same as
+/// the domain
+///
+/// Like there, the length is that of the canonical form, surrounding whitespace does not count.
+pub fn validate_word(word: &str) -> Result<(), ValidationError> {
+    let canonical = text::canonical(word);
+    if canonical.is_empty() {
+        return Err(ValidationError::new("blank")
+            .with_message(Cow::Borrowed("Must not consist of whitespace only")));
+    }
+    if canonical.chars().count() > text::MAX_LEN {
+        return Err(
+            ValidationError::new("too_long").with_message(Cow::Owned(format!(
+                "Must be at most {} characters",
+                text::MAX_LEN
+            ))),
+        );
+    }
+    match text::illegal_char(word) {
+        Some(c) => Err(ValidationError::new("illegal_character")
+            .with_message(Cow::Owned(format!("Illegal character U+{:04X}", c as u32)))),
+        None => Ok(()),
+    }
+}
+
+/// Applies `validate_word` to each translation
+pub fn validate_translations(translations: &[String]) -> Result<(), ValidationError> {
+    for translation in translations {
+        validate_word(translation)?;
+    }
+    Ok(())
+}
+
+/// Collects all messages per field, falls back to a generic message when none is set
+fn collect_errors(error: ValidationErrors) -> BTreeMap<String, Vec<String>> {
     error
         .field_errors()
         .into_iter()
-        .map(|error| {
-            let default_error = format!("{} is required", error.0);
-            error.1[0]
-                .message
-                .as_ref()
-                .unwrap_or(&std::borrow::Cow::Owned(default_error))
-                .to_string()
+        .map(|(field, errors)| {
+            let messages = errors
+                .iter()
+                .map(|e| match &e.message {
+                    Some(message) => message.to_string(),
+                    None => format!("{field} is required"),
+                })
+                .collect();
+            (field.to_string(), messages)
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::voci::Lang;
+    use crate::driving::rest_handler::vocis::CreateTranslationRequest;
+
+    fn request(word: &str, translations: &[&str]) -> CreateTranslationRequest {
+        CreateTranslationRequest {
+            id: None,
+            word: word.to_string(),
+            lang: Lang::fr,
+            sense: None,
+            translations: translations.iter().map(|t| t.to_string()).collect(),
+            translation_lang: Lang::de,
+        }
+    }
+
+    #[test]
+    fn create_request_blank_illegal_or_overlong_rejected() {
+        assert!(request("chien", &["hund"]).validate().is_ok());
+
+        let blank = collect_errors(request("   ", &["hund"]).validate().unwrap_err());
+        let illegal = collect_errors(request("chien", &["hu\u{200B}nd"]).validate().unwrap_err());
+        let overlong = request("chien", &[&"a".repeat(text::MAX_LEN + 1)]).validate();
+
+        assert_eq!(
+            blank["word"],
+            vec!["Must not consist of whitespace only".to_string()]
+        );
+        assert_eq!(
+            illegal["translations"],
+            vec!["Illegal character U+200B".to_string()]
+        );
+        assert!(overlong.is_err());
+    }
+
+    #[test]
+    fn create_request_length_of_canonical_form_checked() {
+        let padded = format!("  {}  ", "a".repeat(text::MAX_LEN));
+        let decomposed = "e\u{301}".repeat(text::MAX_LEN);
+
+        assert!(request(&padded, &[&padded]).validate().is_ok());
+        assert!(request(&decomposed, &[&decomposed]).validate().is_ok());
+        assert!(
+            request(&"a".repeat(text::MAX_LEN + 1), &["hund"])
+                .validate()
+                .is_err()
+        );
+    }
+}
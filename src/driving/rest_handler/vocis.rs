@@ -1,18 +1,30 @@
-use actix_web::web::Json;
+use actix_web::web::{Json, Path, Query};
 use actix_web::{HttpResponse, web};
 use serde::{Deserialize, Serialize};
+use std::time::UNIX_EPOCH;
+use tracing::instrument;
+use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
 use crate::domain;
+use crate::domain::conjugation::{Conjugation, ConjugationError, Form, Table, TenseForms};
 use crate::domain::create_translation::CreateError;
 use crate::domain::delete_translation::DeleteError;
-use crate::domain::ports::TranslationRepository;
+use crate::domain::link::{LinkError, Linked, Relation};
+use crate::domain::ports::{
+    DeckRepository, LinkRepository, RepoUpdateError, RevisionRepository, TranslationRepository,
+};
 use crate::domain::read_translation::ReadError;
+use crate::domain::revision::{Change, Revision, RevisionError, RevisionId};
+use crate::domain::search_translations::SearchError;
+use crate::domain::trash::TrashError;
 use crate::domain::update_translation::UpdateError;
-use crate::domain::voci::{Lang, TranslationRecord};
+use crate::domain::voci::{Gender, Lang, TranslationId, TranslationRecord, TranslationRecordError};
 
-use crate::driving::rest_handler::errors::ApiError;
-use crate::driving::rest_handler::validate::validate;
+use crate::driving::rest_handler::decks::{DeckParam, resolve_access};
+use crate::driving::rest_handler::errors::{ApiError, Problem};
+use crate::driving::rest_handler::session::Caller;
+use crate::driving::rest_handler::validate::{validate, validate_translations, validate_word};
 
 /// Helper function to reduce boilerplate of an OK/Json response
 fn respond_json<T>(data: T) -> Result<Json<T>, ApiError>
@@ -22,13 +34,28 @@ where
     Ok(Json(data))
 }
 
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Deserialize, Serialize, PartialEq, ToSchema)]
 pub struct TranslationResponse {
     pub id: Option<String>,
     pub word: String,
     pub lang: Lang,
+    /// Gender of the article the word was entered with, if any
+    #[serde(default)]
+    pub gender: Option<Gender>,
+    /// Sense of a word with several meanings, if labeled
+    #[serde(default)]
+    pub sense: Option<String>,
     pub translations: Vec<String>,
     pub translation_lang: Lang,
+    /// Conjugation table of a French verb, if attached
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conjugation: Option<Conjugation>,
+    /// Synonyms, antonyms and the word family, if asked for with `links`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<LinkedResponse>,
+    /// When the record was moved to the trash, in seconds since the Unix epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<u64>,
 }
 impl From<TranslationRecord> for TranslationResponse {
     fn from(s: TranslationRecord) -> Self {
@@ -37,36 +64,145 @@ impl From<TranslationRecord> for TranslationResponse {
             id: id.clone(),
             word: word.clone(),
             lang: lang.clone(),
+            gender: s.word().gender(),
+            sense: s.sense().map(str::to_string),
             translations: translations.clone(),
             translation_lang: translation_lang.clone(),
+            conjugation: s.conjugation().cloned(),
+            links: Vec::new(),
+            deleted_at: s
+                .deleted_at()
+                .and_then(|at| at.duration_since(UNIX_EPOCH).ok())
+                .map(|since| since.as_secs()),
         }
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+/// A record linked to a read one
+#[derive(Debug, Deserialize, Serialize, PartialEq, ToSchema)]
+pub struct LinkedResponse {
+    pub relation: Relation,
+    pub id: Option<String>,
+    pub word: String,
+    pub lang: Lang,
+    #[serde(default)]
+    pub sense: Option<String>,
+}
+impl From<Linked> for LinkedResponse {
+    fn from(l: Linked) -> Self {
+        let (id, word, lang, _, _) = l.record.flat();
+        LinkedResponse {
+            relation: l.relation,
+            id: id.clone(),
+            word: word.clone(),
+            lang: lang.clone(),
+            sense: l.record.sense().map(str::to_string),
+        }
+    }
+}
+
+/// Whether to read the linked records along
+#[derive(Clone, Debug, Deserialize, Serialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct LinksParam {
+    /// Include synonyms, antonyms and the word family of the records read
+    #[serde(default)]
+    pub links: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct LinkRequest {
+    /// ID of the record to link to
+    pub to: String,
+    pub relation: Relation,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, ToSchema)]
+pub struct LinkResponse {
+    pub id: String,
+    pub to: String,
+    pub relation: Relation,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Validate, ToSchema)]
 pub struct CreateTranslationRequest {
     pub id: Option<String>,
-    #[validate(length(min = 1, message = "Word is required and must be at least 1 character"))]
+    #[validate(
+        length(min = 1, message = "Word is required"),
+        custom(function = "validate_word")
+    )]
+    #[schema(min_length = 1)]
     pub word: String,
     pub lang: Lang,
+    /// Sense of a word with several meanings, e.g. "fruit" for "avocat"
+    ///
+    /// Creating tells the record apart from those of other senses, updating picks the sense
+    /// to extend.
+    #[serde(default)]
+    #[validate(length(max = 100, message = "Sense must be at most 100 characters"))]
+    #[schema(max_length = 100)]
+    pub sense: Option<String>,
     #[validate(length(
         min = 1,
         message = "ingredients is required and must be at least 1 item"
     ))]
+    #[validate(custom(function = "validate_translations"))]
+    #[schema(min_items = 1)]
     pub translations: Vec<String>,
     pub translation_lang: Lang,
 }
 
-pub async fn create_translation<T: TranslationRepository>(
+/// Create a translation record for a word
+#[utoipa::path(
+    post,
+    path = "/voci/api/v1/translations",
+    tag = "translations",
+    params(DeckParam),
+    request_body = CreateTranslationRequest,
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Translation created", body = TranslationResponse),
+        (
+            status = 400, description = "Invalid input",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "API key missing or unknown",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "API key lacks the write scope or the deck is read-only",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Translation not found",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 409, description = "Translation already exists",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 422, description = "Validation failed",
+            body = Problem, content_type = "application/problem+json"
+        ),
+    )
+)]
+#[instrument(skip_all, fields(word = %request.word, lang = %request.lang))]
+pub async fn create_translation<T: TranslationRepository + DeckRepository + RevisionRepository>(
     repository: web::Data<T>,
+    caller: Caller,
     request: Json<CreateTranslationRequest>,
 ) -> Result<Json<TranslationResponse>, ApiError> {
     validate(&request)?;
+    let access = resolve_access(repository.get_ref(), &caller).await?;
 
     let result = domain::create_translation::create_translation(
         repository.get_ref(),
+        &access,
         &request.word,
         &request.lang,
+        request.sense.as_deref(),
         &request.translations,
         &request.translation_lang,
     )
@@ -75,52 +211,317 @@ pub async fn create_translation<T: TranslationRepository>(
     result
         .map(|v| respond_json(TranslationResponse::from(v)))
         .map_err(|e| match e {
-            CreateError::InvalidInput(s) => ApiError::InvalidInput(s.to_string()),
-            CreateError::Read(s) => ApiError::NotFound(s.to_string()),
-            CreateError::Create(s) => ApiError::BadRequest(s.to_string()),
-            CreateError::Duplicate => ApiError::Conflict(e.to_string()),
+            CreateError::InvalidInput(_) => ApiError::invalid_input(&e),
+            CreateError::Read(_) => ApiError::not_found(&e),
+            CreateError::Create(_) => ApiError::bad_request(&e),
+            CreateError::Duplicate => ApiError::conflict(&e),
+            CreateError::ReadOnly => ApiError::forbidden(&e),
         })?
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize, Validate)]
+#[derive(Clone, Debug, Deserialize, Serialize, Validate, ToSchema)]
 pub struct RequestTranslationByWord {
-    #[validate(length(min = 1, message = "Word is required and must be at least 1 character"))]
+    #[validate(
+        length(min = 1, message = "Word is required"),
+        custom(function = "validate_word")
+    )]
+    #[schema(min_length = 1)]
     pub word: String,
     pub lang: Lang,
+    /// Sense of a word with several meanings, reads return all senses without it
+    #[serde(default)]
+    #[validate(length(max = 100, message = "Sense must be at most 100 characters"))]
+    #[schema(max_length = 100)]
+    pub sense: Option<String>,
 }
 
-pub async fn read_translation<T: TranslationRepository>(
+/// Read the translation records of a word, one per sense
+#[utoipa::path(
+    get,
+    path = "/voci/api/v1/translations",
+    tag = "translations",
+    params(DeckParam, LinksParam),
+    request_body = RequestTranslationByWord,
+    responses(
+        (status = 200, description = "Translations found, one per sense", body = Vec<TranslationResponse>),
+        (
+            status = 400, description = "Invalid input",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Translation not found, with suggestions of nearest words",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 422, description = "Validation failed",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 500, description = "Unknown error",
+            body = Problem, content_type = "application/problem+json"
+        ),
+    )
+)]
+#[instrument(skip_all, fields(word = %request.word, lang = %request.lang))]
+pub async fn read_translation<T: TranslationRepository + DeckRepository + LinkRepository>(
     repository: web::Data<T>,
+    caller: Caller,
+    links: Query<LinksParam>,
     request: Json<RequestTranslationByWord>,
+) -> Result<Json<Vec<TranslationResponse>>, ApiError> {
+    validate(&request)?;
+    let access = resolve_access(repository.get_ref(), &caller).await?;
+
+    let result: Result<Vec<TranslationRecord>, ReadError> =
+        domain::read_translation::read_translation(
+            repository.get_ref(),
+            &access,
+            &request.word,
+            &request.lang,
+            request.sense.as_deref(),
+        )
+        .await;
+
+    let records = result.map_err(|e| match e {
+        ReadError::QueryWord(_) => ApiError::invalid_input(&e),
+        ReadError::RecordNotFound(ref suggestions) => {
+            ApiError::not_found_with_suggestions(&e, suggestions)
+        }
+        ReadError::Unknown => ApiError::unknown(&e),
+    })?;
+    let linked = if links.links {
+        domain::link::linked_records(repository.get_ref(), &access, &records)
+            .await
+            .map_err(|e| ApiError::unknown(&e))?
+    } else {
+        Vec::new()
+    };
+
+    respond_json(
+        records
+            .into_iter()
+            .map(|tr| {
+                let id = tr.id().clone();
+                let mut response = TranslationResponse::from(tr);
+                response.links = linked
+                    .iter()
+                    .filter(|l| l.from == id)
+                    .cloned()
+                    .map(LinkedResponse::from)
+                    .collect();
+                response
+            })
+            .collect(),
+    )
+}
+
+/// Link the translation record `id` to another one as synonym, antonym or word family
+#[utoipa::path(
+    post,
+    path = "/voci/api/v1/translations/{id}/links",
+    tag = "translations",
+    params(("id" = String, Path, description = "ID of the translation record"), DeckParam),
+    request_body = LinkRequest,
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Records linked", body = LinkResponse),
+        (
+            status = 400, description = "Record linked to itself",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "API key missing or unknown",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "API key lacks the write scope or a record is read-only",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Translation not found",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 500, description = "Unknown error",
+            body = Problem, content_type = "application/problem+json"
+        ),
+    )
+)]
+#[instrument(skip_all, fields(id = %id, to = %request.to, relation = %request.relation))]
+pub async fn link_translations<T: TranslationRepository + DeckRepository + LinkRepository>(
+    repository: web::Data<T>,
+    caller: Caller,
+    id: Path<String>,
+    request: Json<LinkRequest>,
+) -> Result<Json<LinkResponse>, ApiError> {
+    let access = resolve_access(repository.get_ref(), &caller).await?;
+
+    let result = domain::link::link_translations(
+        repository.get_ref(),
+        &access,
+        &TranslationId::from(id.as_str()),
+        &TranslationId::from(request.to.as_str()),
+        request.relation,
+    )
+    .await;
+
+    result
+        .map(|link| {
+            respond_json(LinkResponse {
+                id: id.into_inner(),
+                to: request.to.clone(),
+                relation: link.relation(),
+            })
+        })
+        .map_err(|e| match e {
+            LinkError::SelfLink => ApiError::bad_request(&e),
+            LinkError::NotFound => ApiError::not_found(&e),
+            LinkError::ReadOnly => ApiError::forbidden(&e),
+            LinkError::Read(_) | LinkError::Create(_) => ApiError::unknown(&e),
+        })?
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Validate, ToSchema)]
+pub struct ConjugationRequest {
+    /// Infinitive of the French verb
+    #[validate(
+        length(min = 1, message = "Word is required"),
+        custom(function = "validate_word")
+    )]
+    #[schema(min_length = 1)]
+    pub word: String,
+    /// Sense of a verb with several meanings
+    #[serde(default)]
+    #[validate(length(max = 100, message = "Sense must be at most 100 characters"))]
+    #[schema(max_length = 100)]
+    pub sense: Option<String>,
+    /// Generate the table after the regular -er, -ir or -re pattern instead of taking `tenses`
+    #[serde(default)]
+    pub regular: bool,
+    /// Forms of each tense, in the order je, tu, il, nous, vous, ils
+    #[serde(default)]
+    pub tenses: Vec<TenseForms>,
+    /// Single forms replacing those of the table, e.g. of an irregular verb
+    #[serde(default)]
+    pub overrides: Vec<Form>,
+}
+
+/// Attach a conjugation table to the translation record of a French verb
+#[utoipa::path(
+    put,
+    path = "/voci/api/v1/translations/conjugation",
+    tag = "translations",
+    params(DeckParam),
+    request_body = ConjugationRequest,
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Conjugation attached", body = TranslationResponse),
+        (
+            status = 400, description = "Invalid table, verb not regular or sense needed",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "API key missing or unknown",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "API key lacks the write scope or the deck is read-only",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Verb or sense not found",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 422, description = "Validation failed",
+            body = Problem, content_type = "application/problem+json"
+        ),
+    )
+)]
+#[instrument(skip_all, fields(word = %request.word, regular = request.regular))]
+pub async fn conjugate_translation<
+    T: TranslationRepository + DeckRepository + RevisionRepository,
+>(
+    repository: web::Data<T>,
+    caller: Caller,
+    request: Json<ConjugationRequest>,
 ) -> Result<Json<TranslationResponse>, ApiError> {
     validate(&request)?;
+    let access = resolve_access(repository.get_ref(), &caller).await?;
+    let table = if request.regular {
+        Table::Regular
+    } else {
+        Table::Manual(request.tenses.clone())
+    };
 
-    let result: Result<TranslationRecord, ReadError> = domain::read_translation::read_translation(
+    let result = domain::conjugation::conjugate_translation(
         repository.get_ref(),
+        &access,
         &request.word,
-        &request.lang,
+        request.sense.as_deref(),
+        table,
+        &request.overrides,
     )
     .await;
 
     result
         .map(|v| respond_json(TranslationResponse::from(v)))
         .map_err(|e| match e {
-            ReadError::QueryWord(s) => ApiError::InvalidInput(s.to_string()),
-            ReadError::RecordNotFound => ApiError::NotFound(e.to_string()),
-            ReadError::Unknown => ApiError::Unknown(e.to_string()),
+            ConjugationError::Word(TranslationRecordError::UnknownSense(_))
+            | ConjugationError::Read(_)
+            | ConjugationError::Update(_) => ApiError::not_found(&e),
+            ConjugationError::ReadOnly => ApiError::forbidden(&e),
+            _ => ApiError::invalid_input(&e),
         })?
 }
 
-pub async fn update_translation<T: TranslationRepository>(
+/// Add translations to the translation record of a word, of `sense` if it has several
+#[utoipa::path(
+    put,
+    path = "/voci/api/v1/translations",
+    tag = "translations",
+    params(DeckParam),
+    request_body = CreateTranslationRequest,
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Translation updated", body = TranslationResponse),
+        (
+            status = 400, description = "Invalid input or sense needed",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "API key missing or unknown",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "API key lacks the write scope or the deck is read-only",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Translation or sense not found",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 422, description = "Validation failed",
+            body = Problem, content_type = "application/problem+json"
+        ),
+    )
+)]
+#[instrument(skip_all, fields(word = %request.word, lang = %request.lang))]
+pub async fn update_translation<T: TranslationRepository + DeckRepository + RevisionRepository>(
     repository: web::Data<T>,
+    caller: Caller,
     request: Json<CreateTranslationRequest>,
 ) -> Result<Json<TranslationResponse>, ApiError> {
     validate(&request)?;
+    let access = resolve_access(repository.get_ref(), &caller).await?;
 
     let result = domain::update_translation::update_translation(
         repository.get_ref(),
+        &access,
         &request.word,
         &request.lang,
+        request.sense.as_deref(),
         &request.translations,
         &request.translation_lang,
     )
@@ -129,30 +530,386 @@ pub async fn update_translation<T: TranslationRepository>(
     result
         .map(|v| respond_json(TranslationResponse::from(v)))
         .map_err(|e| match e {
-            UpdateError::Word(s) => ApiError::InvalidInput(s.to_string()),
-            UpdateError::Read(s) => ApiError::NotFound(s.to_string()),
-            UpdateError::Update(s) => ApiError::NotFound(s.to_string()),
+            UpdateError::Word(TranslationRecordError::UnknownSense(_)) => ApiError::not_found(&e),
+            UpdateError::Word(_) => ApiError::invalid_input(&e),
+            UpdateError::Read(_) => ApiError::not_found(&e),
+            UpdateError::Update(_) => ApiError::not_found(&e),
+            UpdateError::ReadOnly => ApiError::forbidden(&e),
         })?
 }
 
-pub async fn delete_translation<T: TranslationRepository>(
+/// Delete the translation record of a word, of `sense` if it has several
+#[utoipa::path(
+    delete,
+    path = "/voci/api/v1/translations",
+    tag = "translations",
+    params(DeckParam),
+    request_body = RequestTranslationByWord,
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Translation deleted"),
+        (
+            status = 400, description = "Invalid input or sense needed",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "API key missing or unknown",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "API key lacks the write scope or the deck is read-only",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Sense not found",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 422, description = "Validation failed",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 500, description = "Unknown error",
+            body = Problem, content_type = "application/problem+json"
+        ),
+    )
+)]
+#[instrument(skip_all, fields(word = %request.word, lang = %request.lang))]
+pub async fn delete_translation<T: TranslationRepository + DeckRepository + RevisionRepository>(
     repository: web::Data<T>,
+    caller: Caller,
     request: Json<RequestTranslationByWord>,
 ) -> Result<HttpResponse, ApiError> {
     validate(&request)?;
+    let access = resolve_access(repository.get_ref(), &caller).await?;
 
     let result = domain::delete_translation::delete_translation(
         repository.get_ref(),
+        &access,
         &request.word,
         &request.lang,
+        request.sense.as_deref(),
     )
     .await;
 
     result
         .map(|_| Ok(HttpResponse::Ok().finish()))
         .map_err(|e| match e {
-            DeleteError::Word(s) => ApiError::InvalidInput(s.to_string()),
-            DeleteError::Read(s) => ApiError::InvalidInput(s.to_string()),
-            DeleteError::Delete(s) => ApiError::Unknown(s.to_string()),
+            DeleteError::Word(TranslationRecordError::UnknownSense(_)) => ApiError::not_found(&e),
+            DeleteError::Word(_) => ApiError::invalid_input(&e),
+            DeleteError::Read(_) => ApiError::invalid_input(&e),
+            DeleteError::Delete(_) => ApiError::unknown(&e),
+            DeleteError::ReadOnly => ApiError::forbidden(&e),
+        })?
+}
+
+/// List the translation records in the trash, most recently deleted first
+#[utoipa::path(
+    get,
+    path = "/voci/api/v1/translations/trash",
+    tag = "translations",
+    params(DeckParam),
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Records in the trash", body = [TranslationResponse]),
+        (
+            status = 401, description = "API key missing or unknown",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "The deck is read-only",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 500, description = "Unknown error",
+            body = Problem, content_type = "application/problem+json"
+        ),
+    )
+)]
+#[instrument(skip_all)]
+pub async fn list_trash<T: TranslationRepository + DeckRepository>(
+    repository: web::Data<T>,
+    caller: Caller,
+) -> Result<Json<Vec<TranslationResponse>>, ApiError> {
+    let access = resolve_access(repository.get_ref(), &caller).await?;
+
+    let result = domain::trash::list_trash(repository.get_ref(), &access).await;
+
+    result
+        .map(|trash| respond_json(trash.into_iter().map(TranslationResponse::from).collect()))
+        .map_err(|e| match e {
+            TrashError::ReadOnly => ApiError::forbidden(&e),
+            _ => ApiError::unknown(&e),
         })?
 }
+
+/// Take the translation record `id` out of the trash
+#[utoipa::path(
+    post,
+    path = "/voci/api/v1/translations/{id}/restore",
+    tag = "translations",
+    params(("id" = String, Path, description = "ID of the translation record"), DeckParam),
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Translation restored", body = TranslationResponse),
+        (
+            status = 401, description = "API key missing or unknown",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "API key lacks the write scope or the deck is read-only",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Translation not in the trash",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 409, description = "The word was created again in the same sense",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 500, description = "Unknown error",
+            body = Problem, content_type = "application/problem+json"
+        ),
+    )
+)]
+#[instrument(skip_all, fields(id = %id))]
+pub async fn restore_translation<T: TranslationRepository + DeckRepository + RevisionRepository>(
+    repository: web::Data<T>,
+    caller: Caller,
+    id: Path<String>,
+) -> Result<Json<TranslationResponse>, ApiError> {
+    let access = resolve_access(repository.get_ref(), &caller).await?;
+
+    let result = domain::trash::restore_translation(
+        repository.get_ref(),
+        &access,
+        &TranslationId::from(id.as_str()),
+    )
+    .await;
+
+    result
+        .map(|tr| respond_json(TranslationResponse::from(tr)))
+        .map_err(|e| match e {
+            TrashError::NotFound | TrashError::Update(RepoUpdateError::NotFound) => {
+                ApiError::not_found(&e)
+            }
+            TrashError::Duplicate => ApiError::conflict(&e),
+            TrashError::ReadOnly => ApiError::forbidden(&e),
+            TrashError::Read(_) | TrashError::Update(_) | TrashError::Delete(_) => {
+                ApiError::unknown(&e)
+            }
+        })?
+}
+
+/// A change of a translation record with the record before and after it
+#[derive(Debug, Deserialize, Serialize, PartialEq, ToSchema)]
+pub struct RevisionResponse {
+    pub id: Option<String>,
+    pub change: Change,
+    /// The user who made the change
+    pub actor: String,
+    /// When the change was made, in seconds since the Unix epoch
+    pub at: u64,
+    /// The record before the change, missing for a creation
+    #[serde(default)]
+    pub before: Option<TranslationResponse>,
+    /// The record after the change, missing for a deletion
+    #[serde(default)]
+    pub after: Option<TranslationResponse>,
+}
+impl From<Revision> for RevisionResponse {
+    fn from(r: Revision) -> Self {
+        RevisionResponse {
+            id: r.id().map(|id| id.value().to_string()),
+            change: r.change(),
+            actor: r.actor().value().to_string(),
+            at: r
+                .at()
+                .duration_since(UNIX_EPOCH)
+                .map(|since| since.as_secs())
+                .unwrap_or_default(),
+            before: r.before().cloned().map(TranslationResponse::from),
+            after: r.after().cloned().map(TranslationResponse::from),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct RevertRequest {
+    /// ID of the revision whose resulting record is restored
+    pub revision: String,
+}
+
+/// List the revisions of the translation record `id`, oldest first
+#[utoipa::path(
+    get,
+    path = "/voci/api/v1/translations/{id}/history",
+    tag = "translations",
+    params(("id" = String, Path, description = "ID of the translation record"), DeckParam),
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Revisions of the record", body = [RevisionResponse]),
+        (
+            status = 401, description = "API key missing or unknown",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Translation not found",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 500, description = "Unknown error",
+            body = Problem, content_type = "application/problem+json"
+        ),
+    )
+)]
+#[instrument(skip_all, fields(id = %id))]
+pub async fn translation_history<T: DeckRepository + RevisionRepository>(
+    repository: web::Data<T>,
+    caller: Caller,
+    id: Path<String>,
+) -> Result<Json<Vec<RevisionResponse>>, ApiError> {
+    let access = resolve_access(repository.get_ref(), &caller).await?;
+
+    let result = domain::revision::translation_history(
+        repository.get_ref(),
+        &access,
+        &TranslationId::from(id.as_str()),
+    )
+    .await;
+
+    result
+        .map(|revisions| respond_json(revisions.into_iter().map(RevisionResponse::from).collect()))
+        .map_err(map_revision_error)?
+}
+
+/// Set the translation record `id` back to its state after one of its revisions
+#[utoipa::path(
+    post,
+    path = "/voci/api/v1/translations/{id}/revert",
+    tag = "translations",
+    params(("id" = String, Path, description = "ID of the translation record"), DeckParam),
+    request_body = RevertRequest,
+    security(("api_key" = [])),
+    responses(
+        (status = 200, description = "Translation reverted", body = TranslationResponse),
+        (
+            status = 400, description = "The revision deleted the record",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 401, description = "API key missing or unknown",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 403, description = "API key lacks the write scope or the deck is read-only",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 404, description = "Translation or revision not found",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 500, description = "Unknown error",
+            body = Problem, content_type = "application/problem+json"
+        ),
+    )
+)]
+#[instrument(skip_all, fields(id = %id, revision = %request.revision))]
+pub async fn revert_translation<T: TranslationRepository + DeckRepository + RevisionRepository>(
+    repository: web::Data<T>,
+    caller: Caller,
+    id: Path<String>,
+    request: Json<RevertRequest>,
+) -> Result<Json<TranslationResponse>, ApiError> {
+    let access = resolve_access(repository.get_ref(), &caller).await?;
+
+    let result = domain::revision::revert_translation(
+        repository.get_ref(),
+        &access,
+        &TranslationId::from(id.as_str()),
+        &RevisionId::new(&request.revision),
+    )
+    .await;
+
+    result
+        .map(|tr| respond_json(TranslationResponse::from(tr)))
+        .map_err(map_revision_error)?
+}
+
+fn map_revision_error(e: RevisionError) -> ApiError {
+    match e {
+        RevisionError::NotFound
+        | RevisionError::UnknownRevision
+        | RevisionError::Update(RepoUpdateError::NotFound) => ApiError::not_found(&e),
+        RevisionError::Deletion => ApiError::bad_request(&e),
+        RevisionError::ReadOnly => ApiError::forbidden(&e),
+        RevisionError::Read(_) | RevisionError::Update(_) => ApiError::unknown(&e),
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchRequest {
+    /// Prefix, part or accent-free form of a word or translation
+    #[validate(
+        length(min = 1, message = "q is required"),
+        custom(function = "validate_word")
+    )]
+    #[param(min_length = 1)]
+    pub q: String,
+    /// Only match words or translations of this language
+    pub lang: Option<Lang>,
+}
+
+/// Search words and translations, ignoring letter case and accents
+///
+/// Exact matches come first, then words or translations starting with `q`, then those
+/// containing it.
+#[utoipa::path(
+    get,
+    path = "/voci/api/v1/translations/search",
+    tag = "translations",
+    params(SearchRequest, DeckParam),
+    responses(
+        (status = 200, description = "Matching translations, best first", body = Vec<TranslationResponse>),
+        (
+            status = 400, description = "Invalid query",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 422, description = "Validation failed",
+            body = Problem, content_type = "application/problem+json"
+        ),
+        (
+            status = 500, description = "Unknown error",
+            body = Problem, content_type = "application/problem+json"
+        ),
+    )
+)]
+#[instrument(skip_all, fields(q = %request.q))]
+pub async fn search_translations<T: TranslationRepository + DeckRepository>(
+    repository: web::Data<T>,
+    caller: Caller,
+    request: Query<SearchRequest>,
+) -> Result<Json<Vec<TranslationResponse>>, ApiError> {
+    validate(&request)?;
+    let access = resolve_access(repository.get_ref(), &caller).await?;
+
+    let result = domain::search_translations::search_translations(
+        repository.get_ref(),
+        &access,
+        &request.q,
+        request.lang.as_ref(),
+    )
+    .await;
+
+    result
+        .map(|v| Json(v.into_iter().map(TranslationResponse::from).collect()))
+        .map_err(|e| match e {
+            SearchError::EmptyQuery => ApiError::invalid_input(&e),
+            SearchError::Unknown => ApiError::unknown(&e),
+        })
+}
@@ -1,61 +1,352 @@
+#[cfg(feature = "client")]
+pub mod client;
 pub mod config;
 pub mod domain;
 pub mod driven;
 pub mod driving;
+pub mod telemetry;
 pub mod test_utils;
 
 pub mod server {
 
     use actix_web::dev::Server;
-    use actix_web::middleware::Logger;
+    use actix_web::middleware::{Logger, from_fn};
     use actix_web::{App, HttpServer, web, web::Data};
+    use rustls::pki_types::pem::PemObject;
+    use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+    use std::io;
+    use std::path::Path;
+    use std::sync::Arc;
+    use std::time::Duration;
 
-    use crate::domain::ports::TranslationRepository;
+    use crate::config::ServerConfig;
+    use crate::domain;
+    use crate::domain::ports::{
+        DeckRepository, LinkRepository, ProgressRepository, TranslationRepository, UserRepository,
+    };
     use crate::driven::repository::mongo_repository::VociMongoRepository;
     use crate::driving::rest_handler;
+    use crate::driving::rest_handler::session::TokenIssuer;
+    use crate::telemetry;
 
     pub async fn create_server(
-        repo: impl TranslationRepository,
-        port: u16,
+        repo: impl TranslationRepository + UserRepository + DeckRepository + ProgressRepository,
+        config: &ServerConfig,
     ) -> Result<Server, std::io::Error> {
-        let server = HttpServer::new(move || {
+        let metrics = telemetry::install();
+        let auth = config.auth.clone();
+        if !auth.enabled() {
+            tracing::warn!("No API keys configured, the REST API is not protected");
+        }
+        let secret = auth
+            .token_secret
+            .clone()
+            .map(String::into_bytes)
+            .unwrap_or_else(|| {
+                tracing::warn!("No token secret configured, login tokens end with the process");
+                rand::random::<[u8; 32]>().to_vec()
+            });
+        let issuer = TokenIssuer::new(&secret, auth.token_ttl);
+        let mut server = HttpServer::new(move || {
             App::new()
+                .wrap(from_fn(rest_handler::metrics::track_requests))
                 .wrap(Logger::default())
+                .wrap(from_fn(rest_handler::request_id::trace_request))
                 .app_data(Data::new(repo.clone()))
+                .app_data(Data::new(metrics.clone()))
+                .app_data(Data::new(auth.clone()))
+                .app_data(Data::new(issuer.clone()))
                 .configure(routes)
-        })
-        .bind(("127.0.0.1", port))?
-        .run();
-        Ok(server)
+        });
+
+        if let Some(workers) = config.workers {
+            server = server.workers(workers);
+        }
+        if let Some(keep_alive) = config.keep_alive {
+            server = server.keep_alive(Duration::from_secs(keep_alive));
+        }
+
+        let address = (config.host.as_str(), config.port);
+        let server = match (&config.tls_cert, &config.tls_key) {
+            (Some(cert), Some(key)) => server.bind_rustls_0_23(address, tls_config(cert, key)?)?,
+            (None, None) => server.bind(address)?,
+            _ => {
+                return Err(io::Error::other(
+                    "TLS needs both a certificate and a private key",
+                ));
+            }
+        };
+
+        Ok(server.run())
+    }
+
+    /// Purges the records in the trash for longer than `trash_days` once an hour, 0 keeps them
+    pub fn spawn_trash_purge(
+        repo: impl TranslationRepository + LinkRepository + 'static,
+        trash_days: u32,
+    ) {
+        if trash_days == 0 {
+            return;
+        }
+        let retention = Duration::from_secs(u64::from(trash_days) * 24 * 60 * 60);
+        actix_rt::spawn(async move {
+            let mut interval = actix_rt::time::interval(Duration::from_secs(60 * 60));
+            loop {
+                interval.tick().await;
+                match domain::trash::purge_trash(&repo, retention).await {
+                    Ok(0) => {}
+                    Ok(purged) => tracing::info!(purged, "Purged records from the trash"),
+                    Err(e) => tracing::warn!("Cannot purge the trash: {e}"),
+                }
+            }
+        });
+    }
+
+    /// Loads the PEM encoded certificate chain and private key
+    fn tls_config(cert: &Path, key: &Path) -> Result<rustls::ServerConfig, io::Error> {
+        let certs = CertificateDer::pem_file_iter(cert)
+            .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| io::Error::other(format!("Cannot read TLS certificate: {e}")))?;
+        let key = PrivateKeyDer::from_pem_file(key)
+            .map_err(|e| io::Error::other(format!("Cannot read TLS private key: {e}")))?;
+
+        rustls::ServerConfig::builder_with_provider(Arc::new(
+            rustls::crypto::ring::default_provider(),
+        ))
+        .with_safe_default_protocol_versions()
+        .and_then(|builder| builder.with_no_client_auth().with_single_cert(certs, key))
+        .map_err(io::Error::other)
     }
 
     fn routes(cfg: &mut web::ServiceConfig) {
+        cfg.service(web::resource("/metrics").route(web::get().to(rest_handler::metrics::metrics)));
         cfg.service(
-            web::scope("/voci").service(
-                web::scope("/api/v1")
-                    .service(
-                        web::resource("translations")
-                            .route(
-                                web::get().to(rest_handler::vocis::read_translation::<
-                                    VociMongoRepository,
-                                >),
-                            )
-                            .route(
-                                web::post().to(rest_handler::vocis::create_translation::<
-                                    VociMongoRepository,
-                                >),
-                            )
-                            .route(
-                                web::delete().to(rest_handler::vocis::delete_translation::<
-                                    VociMongoRepository,
-                                >),
-                            )
-                            .route(web::put().to(rest_handler::vocis::update_translation::<
-                                VociMongoRepository,
-                            >)),
-                    )
-                    .service(web::resource("translations/{id}")),
-            ),
+            web::scope("/voci")
+                .service(
+                    web::scope("/health")
+                        .service(
+                            web::resource("live").route(web::get().to(rest_handler::health::live)),
+                        )
+                        .service(web::resource("ready").route(
+                            web::get().to(rest_handler::health::ready::<VociMongoRepository>),
+                        )),
+                )
+                .service(
+                    web::scope("/api/v1")
+                        .service(
+                            web::resource("translations")
+                                .wrap(from_fn(rest_handler::auth::authorize))
+                                .route(web::get().to(rest_handler::vocis::read_translation::<
+                                    VociMongoRepository,
+                                >))
+                                .route(web::post().to(rest_handler::vocis::create_translation::<
+                                    VociMongoRepository,
+                                >))
+                                .route(web::delete().to(rest_handler::vocis::delete_translation::<
+                                    VociMongoRepository,
+                                >))
+                                .route(web::put().to(rest_handler::vocis::update_translation::<
+                                    VociMongoRepository,
+                                >)),
+                        )
+                        .service(
+                            web::resource("translations/search")
+                                .wrap(from_fn(rest_handler::auth::authorize))
+                                .route(web::get().to(rest_handler::vocis::search_translations::<
+                                    VociMongoRepository,
+                                >)),
+                        )
+                        .service(
+                            web::resource("translations/conjugation")
+                                .wrap(from_fn(rest_handler::auth::authorize))
+                                .route(
+                                    web::put().to(rest_handler::vocis::conjugate_translation::<
+                                        VociMongoRepository,
+                                    >),
+                                ),
+                        )
+                        .service(
+                            web::resource("translations/trash")
+                                .wrap(from_fn(rest_handler::auth::authorize))
+                                .route(
+                                    web::get()
+                                        .to(rest_handler::vocis::list_trash::<VociMongoRepository>),
+                                ),
+                        )
+                        .service(web::resource("translations/{id}"))
+                        .service(
+                            web::resource("translations/{id}/restore")
+                                .wrap(from_fn(rest_handler::auth::authorize))
+                                .route(web::post().to(rest_handler::vocis::restore_translation::<
+                                    VociMongoRepository,
+                                >)),
+                        )
+                        .service(
+                            web::resource("translations/{id}/history")
+                                .wrap(from_fn(rest_handler::auth::authorize))
+                                .route(web::get().to(rest_handler::vocis::translation_history::<
+                                    VociMongoRepository,
+                                >)),
+                        )
+                        .service(
+                            web::resource("translations/{id}/revert")
+                                .wrap(from_fn(rest_handler::auth::authorize))
+                                .route(web::post().to(rest_handler::vocis::revert_translation::<
+                                    VociMongoRepository,
+                                >)),
+                        )
+                        .service(
+                            web::resource("translations/{id}/links")
+                                .wrap(from_fn(rest_handler::auth::authorize))
+                                .route(web::post().to(rest_handler::vocis::link_translations::<
+                                    VociMongoRepository,
+                                >)),
+                        )
+                        .service(
+                            web::resource("users")
+                                .wrap(from_fn(rest_handler::auth::authorize_admin))
+                                .route(
+                                    web::post().to(rest_handler::accounts::register::<
+                                        VociMongoRepository,
+                                    >),
+                                ),
+                        )
+                        .service(
+                            // Logging in changes nothing, it is how users without a write key
+                            // get their token, so it needs at most the read scope
+                            web::resource("login")
+                                .wrap(from_fn(rest_handler::auth::authorize_read))
+                                .route(
+                                    web::post()
+                                        .to(rest_handler::accounts::login::<VociMongoRepository>),
+                                ),
+                        )
+                        .service(
+                            web::resource("decks")
+                                .wrap(from_fn(rest_handler::auth::authorize))
+                                .route(
+                                    web::post().to(rest_handler::decks::create_deck::<
+                                        VociMongoRepository,
+                                    >),
+                                ),
+                        )
+                        .service(
+                            web::resource("decks/{id}/editors")
+                                .wrap(from_fn(rest_handler::auth::authorize))
+                                .route(
+                                    web::post()
+                                        .to(rest_handler::decks::add_editor::<VociMongoRepository>),
+                                ),
+                        )
+                        .service(
+                            web::resource("decks/{id}/subscription")
+                                .wrap(from_fn(rest_handler::auth::authorize))
+                                .route(
+                                    web::put()
+                                        .to(rest_handler::decks::subscribe::<VociMongoRepository>),
+                                )
+                                .route(
+                                    web::delete().to(rest_handler::decks::unsubscribe::<
+                                        VociMongoRepository,
+                                    >),
+                                ),
+                        )
+                        .service(
+                            web::resource("quiz")
+                                .wrap(from_fn(rest_handler::auth::authorize))
+                                .route(
+                                    web::get()
+                                        .to(rest_handler::quiz::draw_quiz::<VociMongoRepository>),
+                                ),
+                        )
+                        .service(
+                            web::resource("quiz/conjugation")
+                                .wrap(from_fn(rest_handler::auth::authorize))
+                                .route(web::get().to(rest_handler::quiz::draw_conjugation::<
+                                    VociMongoRepository,
+                                >))
+                                .route(web::post().to(rest_handler::quiz::answer_conjugation::<
+                                    VociMongoRepository,
+                                >)),
+                        )
+                        .service(
+                            web::resource("quiz/answer")
+                                .wrap(from_fn(rest_handler::auth::authorize))
+                                .route(
+                                    web::post()
+                                        .to(rest_handler::quiz::answer_quiz::<VociMongoRepository>),
+                                ),
+                        )
+                        .service(
+                            web::resource("openapi.json")
+                                .route(web::get().to(rest_handler::openapi::openapi_json)),
+                        )
+                        .service(
+                            web::resource("docs").route(web::get().to(rest_handler::openapi::docs)),
+                        )
+                        .service(
+                            web::resource("docs/redoc.standalone.js")
+                                .route(web::get().to(rest_handler::openapi::redoc_js)),
+                        ),
+                ),
         );
     }
+
+    #[cfg(test)]
+    mod tests {
+        use actix_web::http::StatusCode;
+        use actix_web::test;
+
+        use super::*;
+        use crate::config::AuthConfig;
+        use crate::domain::auth::{ApiKey, Scope, hash_api_key};
+
+        fn protected() -> AuthConfig {
+            AuthConfig {
+                keys: vec![ApiKey {
+                    name: "writer".to_string(),
+                    hash: hash_api_key("wr1te"),
+                    scopes: vec![Scope::Write],
+                }],
+                ..AuthConfig::default()
+            }
+        }
+
+        #[actix_rt::test]
+        async fn tls_config_missing_or_invalid_pem_error() {
+            let dir = std::env::temp_dir().join("voci_tls_config");
+            std::fs::create_dir_all(&dir).unwrap();
+            let garbage = dir.join("garbage.pem");
+            std::fs::write(&garbage, "not a certificate").unwrap();
+            let missing = dir.join("missing.pem");
+
+            let missing_err = tls_config(&missing, &missing).unwrap_err();
+            let garbage_err = tls_config(&garbage, &garbage).unwrap_err();
+            std::fs::remove_dir_all(&dir).unwrap();
+
+            assert!(
+                missing_err
+                    .to_string()
+                    .starts_with("Cannot read TLS certificate")
+            );
+            assert!(garbage_err.to_string().starts_with("Cannot read TLS"));
+        }
+
+        #[actix_rt::test]
+        async fn answering_quiz_without_key_unauthorized() {
+            let app = test::init_service(
+                App::new()
+                    .app_data(Data::new(protected()))
+                    .configure(routes),
+            )
+            .await;
+
+            for uri in ["/voci/api/v1/quiz/answer", "/voci/api/v1/quiz/conjugation"] {
+                let request = test::TestRequest::post().uri(uri).to_request();
+                let status = test::call_service(&app, request).await.status();
+
+                assert_eq!(status, StatusCode::UNAUTHORIZED, "{uri}");
+            }
+        }
+    }
 }
@@ -1,20 +1,63 @@
-use env_logger::Env;
-use vocabulaire::config::parse_local_config;
+use clap::Parser;
+use std::process::ExitCode;
+use vocabulaire::config::{config_path, parse_config};
 use vocabulaire::domain::ports::TranslationRepository;
 use vocabulaire::driven::repository::mongo_repository::VociMongoRepository;
+use vocabulaire::driving::cli::{Cli, Command, commands};
 use vocabulaire::server;
+use vocabulaire::telemetry;
 
 #[actix_web::main]
-async fn main() {
-    env_logger::init_from_env(Env::new().filter_or("VOCI_LOG", "debug"));
+async fn main() -> ExitCode {
+    telemetry::init_logging();
 
-    let config = parse_local_config();
+    let cli = Cli::parse();
 
-    let repo = VociMongoRepository::new(&config.persistence).unwrap();
+    let config = match parse_config(config_path(cli.config)) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
 
-    server::create_server(repo, 8082)
-        .await
-        .unwrap()
-        .await
-        .expect("An error occurred while starting the web application");
+    let repo = match VociMongoRepository::new(&config.persistence) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match cli.command.unwrap_or_default() {
+        Command::Serve { port } => {
+            let mut server_config = config.server;
+            if let Some(port) = port {
+                server_config.port = port;
+            }
+
+            server::spawn_trash_purge(repo.clone(), config.persistence.trash_days);
+            let server = match server::create_server(repo, &server_config).await {
+                Ok(server) => server,
+                Err(e) => {
+                    eprintln!("Cannot start the server: {e}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            if let Err(e) = server.await {
+                eprintln!("Server error: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+        command => {
+            if let Err(e) =
+                commands::run(&repo, cli.user.as_deref(), cli.deck.as_deref(), command).await
+            {
+                eprintln!("{e}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    ExitCode::SUCCESS
 }
@@ -0,0 +1,197 @@
+//! Logs and metrics of the service
+//!
+//! Logs are `tracing` events, written as text or JSON lines to stderr.
+//! Metrics are recorded through the `metrics` facade and exposed in Prometheus text format.
+//! Recording is a no-op until `install` registered the Prometheus recorder, e.g. in the
+//! CLI or in unit tests.
+
+use metrics::{Unit, counter, describe_counter, describe_histogram, histogram};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+use std::env;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tracing_subscriber::EnvFilter;
+
+/// Log filter directives, e.g. `info,vocabulaire=debug`
+pub const LOG_FILTER_VAR: &str = "VOCI_LOG";
+/// Log output format, `text` or `json`
+pub const LOG_FORMAT_VAR: &str = "VOCI_LOG_FORMAT";
+const DEFAULT_LOG_FILTER: &str = "debug";
+
+#[derive(Debug, Default, PartialEq)]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(format!(
+                "Unsupported log format: {s}, expected text or json"
+            )),
+        }
+    }
+}
+
+/// Sets up the global `tracing` subscriber from `VOCI_LOG` and `VOCI_LOG_FORMAT`
+///
+/// Records of the `log` crate, e.g. actix's access log, are forwarded to it as well.
+pub fn init_logging() {
+    let filter = EnvFilter::try_from_env(LOG_FILTER_VAR)
+        .unwrap_or_else(|_| EnvFilter::new(DEFAULT_LOG_FILTER));
+    let format = env::var(LOG_FORMAT_VAR)
+        .ok()
+        .map(|f| {
+            f.parse().unwrap_or_else(|e| {
+                eprintln!("{e}");
+                LogFormat::default()
+            })
+        })
+        .unwrap_or_default();
+
+    let subscriber = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr);
+    match format {
+        LogFormat::Text => subscriber.init(),
+        LogFormat::Json => subscriber
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .init(),
+    }
+}
+
+pub const HTTP_REQUESTS: &str = "voci_http_requests_total";
+pub const HTTP_REQUEST_DURATION: &str = "voci_http_request_duration_seconds";
+pub const REPOSITORY_DURATION: &str = "voci_repository_operation_duration_seconds";
+pub const TRANSLATIONS_CREATED: &str = "voci_translations_created_total";
+pub const DUPLICATES_REJECTED: &str = "voci_translations_duplicate_rejected_total";
+pub const QUIZ_ANSWERS: &str = "voci_quiz_answers_total";
+
+/// Upper bounds of the latency histograms, in seconds
+const DURATION_BUCKETS: &[f64] = &[
+    0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
+/// Registers the Prometheus recorder as global recorder
+///
+/// Safe to call more than once, all servers of the process share the recorder.
+pub fn install() -> PrometheusHandle {
+    HANDLE
+        .get_or_init(|| {
+            let handle = PrometheusBuilder::new()
+                .set_buckets_for_metric(
+                    Matcher::Suffix("duration_seconds".to_string()),
+                    DURATION_BUCKETS,
+                )
+                .expect("buckets are not empty")
+                .install_recorder()
+                .expect("no other metrics recorder is installed");
+            describe();
+            handle
+        })
+        .clone()
+}
+
+fn describe() {
+    describe_counter!(HTTP_REQUESTS, "HTTP requests by method, route and status");
+    describe_histogram!(
+        HTTP_REQUEST_DURATION,
+        Unit::Seconds,
+        "HTTP request latency by method, route and status"
+    );
+    describe_histogram!(
+        REPOSITORY_DURATION,
+        Unit::Seconds,
+        "Repository operation latency by method"
+    );
+    describe_counter!(TRANSLATIONS_CREATED, "Translations created");
+    describe_counter!(
+        DUPLICATES_REJECTED,
+        "Translations rejected because the word exists"
+    );
+    describe_counter!(QUIZ_ANSWERS, "Graded quiz answers by result");
+}
+
+pub fn record_request(method: &str, route: &str, status: u16, elapsed: Duration) {
+    let labels = [
+        ("method", method.to_string()),
+        ("route", route.to_string()),
+        ("status", status.to_string()),
+    ];
+    counter!(HTTP_REQUESTS, &labels).increment(1);
+    histogram!(HTTP_REQUEST_DURATION, &labels).record(elapsed);
+}
+
+pub fn translation_created() {
+    counter!(TRANSLATIONS_CREATED).increment(1);
+}
+
+pub fn duplicate_rejected() {
+    counter!(DUPLICATES_REJECTED).increment(1);
+}
+
+pub fn quiz_answered(correct: bool) {
+    let result = if correct { "correct" } else { "incorrect" };
+    counter!(QUIZ_ANSWERS, "result" => result).increment(1);
+}
+
+/// Records the duration of a repository operation when dropped
+pub struct RepositoryTimer {
+    method: &'static str,
+    start: Instant,
+}
+
+impl RepositoryTimer {
+    pub fn start(method: &'static str) -> Self {
+        RepositoryTimer {
+            method,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for RepositoryTimer {
+    fn drop(&mut self) {
+        histogram!(REPOSITORY_DURATION, "method" => self.method).record(self.start.elapsed());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_format_parsed_case_insensitive() {
+        assert_eq!("JSON".parse(), Ok(LogFormat::Json));
+        assert_eq!("text".parse(), Ok(LogFormat::Text));
+        assert!("xml".parse::<LogFormat>().is_err());
+    }
+
+    #[test]
+    fn recorded_metrics_rendered() {
+        let handle = install();
+        install();
+
+        {
+            let _timer = RepositoryTimer::start("read_by_word");
+        }
+        quiz_answered(false);
+
+        let rendered = handle.render();
+        assert!(rendered.contains(r#"voci_quiz_answers_total{result="incorrect"}"#));
+        assert!(rendered.contains(
+            r#"voci_repository_operation_duration_seconds_bucket{method="read_by_word",le="0.001"}"#
+        ));
+    }
+}
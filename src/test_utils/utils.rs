@@ -5,12 +5,17 @@ pub mod shared {
     use std::path::PathBuf;
 
     use crate::config::{Config, PersistenceConfig, parse_config};
-    use crate::domain::ports::TranslationRepository;
+    use crate::domain::create_translation::create_translation;
+    use crate::domain::deck::Access;
+    use crate::domain::ports::{RevisionRepository, TranslationRepository};
+    use crate::domain::user::UserId;
     use crate::domain::voci::{Lang, TranslationRecord};
     use crate::driven::repository::mongo_repository::VociMongoRepository;
 
     /// Constants
     pub const TRANSLATION_ID: &str = "123";
+    pub const OWNER: &str = "alice";
+    pub const OTHER_OWNER: &str = "bob";
     pub const _EMPTY_TRANSLATION_ID: &str = "";
     pub const WORD: &str = "chien";
     pub const WORD_LANG: Lang = Lang::fr;
@@ -18,9 +23,19 @@ pub mod shared {
     pub const ADDITONAL_TRANSLATIONS: [&str; 2] = ["Schäfer", "Jagdhund"];
     pub const TRANSLATION_LANG: Lang = Lang::de;
 
+    pub fn owner() -> UserId {
+        UserId::new(OWNER)
+    }
+
+    /// Own records of `OWNER` only
+    pub fn access() -> Access {
+        Access::own(&owner())
+    }
+
     pub fn stub_translation_record(with_id: bool) -> TranslationRecord {
         TranslationRecord::new(
             if with_id { Some(TRANSLATION_ID) } else { None },
+            &owner(),
             WORD,
             &WORD_LANG,
             &TRANSLATIONS,
@@ -29,6 +44,43 @@ pub mod shared {
         .unwrap()
     }
 
+    /// Creates `word` translated as itself through the create use case
+    pub async fn create_word(
+        repo: &(impl TranslationRepository + RevisionRepository),
+        access: &Access,
+        word: &str,
+    ) -> TranslationRecord {
+        create_translation(
+            repo,
+            access,
+            word,
+            &WORD_LANG,
+            None,
+            &[word],
+            &TRANSLATION_LANG,
+        )
+        .await
+        .unwrap()
+    }
+
+    /// Two senses of "avocat", "lawyer" translated as "Anwalt" and "fruit" as "Avocado"
+    pub async fn create_homographs(repo: &impl TranslationRepository) {
+        for (sense, translation) in [("lawyer", "Anwalt"), ("fruit", "Avocado")] {
+            let tr = TranslationRecord::new(
+                None,
+                &owner(),
+                "avocat",
+                &WORD_LANG,
+                &[translation],
+                &TRANSLATION_LANG,
+            )
+            .unwrap()
+            .with_sense(Some(sense))
+            .unwrap();
+            repo.create(&tr).await.unwrap();
+        }
+    }
+
     pub fn assert_on_translations(actual: &[String], expected: &[String]) {
         assert_eq!(actual.len(), expected.len());
         for (i, item) in expected.iter().enumerate() {
@@ -60,7 +112,7 @@ pub mod shared {
     pub fn get_testing_config() -> Config {
         let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         d.push("src/test_utils/test_config.toml");
-        parse_config(d)
+        parse_config(d).unwrap()
     }
 
     pub async fn setup_repo() -> VociMongoRepository {
@@ -72,12 +124,20 @@ pub mod shared {
         repo
     }
 
+    /// Empties the translation, user, deck, progress, link and revision collections
     pub async fn delete_collection(config: PersistenceConfig, repo: &VociMongoRepository) {
         let collection = repo.get_collection().await;
-        let coll: Collection<VociMongoRepository> = collection
-            .client()
-            .database(&config.database)
-            .collection(&config.schema_collection);
-        coll.delete_many(doc! {}).await.unwrap();
+        let database = collection.client().database(&config.database);
+        for name in [
+            &config.schema_collection,
+            &config.user_collection,
+            &config.deck_collection,
+            &config.progress_collection,
+            &config.link_collection,
+            &config.revision_collection,
+        ] {
+            let coll: Collection<VociMongoRepository> = database.collection(name);
+            coll.delete_many(doc! {}).await.unwrap();
+        }
     }
 }
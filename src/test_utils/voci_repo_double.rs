@@ -2,12 +2,23 @@
 pub mod repo_double {
     use async_trait::async_trait;
     use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use std::time::SystemTime;
 
     use crate::config::PersistenceConfig;
+    use crate::domain::deck::{Deck, DeckId};
+    use crate::domain::link::Link;
     use crate::domain::ports::{
-        RepoCreateError, RepoDeleteError, RepoReadError, RepoUpdateError, TranslationRepository,
+        DeckRepository, LinkRepository, ProgressRepository, RepoCreateError, RepoDeleteError,
+        RepoHealthError, RepoReadError, RepoUpdateError, RevisionRepository, TranslationRepository,
+        UserRepository,
     };
-    use crate::domain::voci::{TranslationId, TranslationRecord, Word};
+    use crate::domain::quiz::Progress;
+    use crate::domain::revision::Revision;
+    use crate::domain::text;
+    use crate::domain::user::{User, UserId};
+    use crate::domain::voci::{Lang, TranslationId, TranslationRecord, Word};
     use crate::test_utils::utils::shared::*;
 
     #[derive(Clone)]
@@ -18,12 +29,68 @@ pub mod repo_double {
     #[derive(Clone)]
     pub struct VociRepoDouble {
         has_error: Wrap,
+        users: Arc<Mutex<Vec<User>>>,
+        decks: Arc<Mutex<Vec<Deck>>>,
+        progress: Arc<Mutex<HashMap<(UserId, String), Progress>>>,
+        /// Created records, kept besides the stub record, trashed ones included
+        records: Arc<Mutex<Vec<TranslationRecord>>>,
+        links: Arc<Mutex<Vec<Link>>>,
+        revisions: Arc<Mutex<Vec<Revision>>>,
     }
 
     impl VociRepoDouble {
         pub fn set_error(&mut self, value: bool) {
             *self.has_error.0.borrow_mut() = value;
         }
+
+        /// `OWNER` and every deck have the stub record
+        fn has_record(&self, owner: &UserId) -> bool {
+            owner.value() == OWNER
+                || self
+                    .decks
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .any(|d| d.vocabulary() == *owner)
+        }
+
+        fn record_of(owner: &UserId) -> TranslationRecord {
+            let stub = stub_translation_record(false);
+            let (id, word, lang, translations, translation_lang) = stub.flat();
+            TranslationRecord::new(
+                id.as_deref(),
+                owner,
+                word,
+                lang,
+                translations,
+                translation_lang,
+            )
+            .unwrap()
+        }
+
+        fn created_of(&self, owner: &UserId) -> Vec<TranslationRecord> {
+            self.records
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|tr| tr.owner() == owner && tr.deleted_at().is_none())
+                .cloned()
+                .collect()
+        }
+
+        fn update_deck(
+            &self,
+            id: &DeckId,
+            change: impl FnOnce(&Deck) -> Deck,
+        ) -> Result<Deck, RepoUpdateError> {
+            let mut decks = self.decks.lock().unwrap();
+            let deck = decks
+                .iter_mut()
+                .find(|d| d.id() == id)
+                .ok_or(RepoUpdateError::NotFound)?;
+            *deck = change(deck);
+            Ok(deck.clone())
+        }
     }
 
     #[async_trait]
@@ -34,6 +101,12 @@ pub mod repo_double {
         {
             Ok(VociRepoDouble {
                 has_error: Wrap(RefCell::from(false)),
+                users: Arc::new(Mutex::new(Vec::new())),
+                decks: Arc::new(Mutex::new(Vec::new())),
+                progress: Arc::new(Mutex::new(HashMap::new())),
+                records: Arc::new(Mutex::new(Vec::new())),
+                links: Arc::new(Mutex::new(Vec::new())),
+                revisions: Arc::new(Mutex::new(Vec::new())),
             })
         }
 
@@ -46,25 +119,116 @@ pub mod repo_double {
             }
 
             let (_id, word, lang, translations, translation_lang) = tr.flat();
+            let mut records = self.records.lock().unwrap();
 
             let s = TranslationRecord::new(
-                Some(TRANSLATION_ID),
+                Some(&format!("{TRANSLATION_ID}-{}", records.len() + 1)),
+                tr.owner(),
                 word,
                 lang,
                 translations,
                 translation_lang,
             )
+            .unwrap()
+            .with_gender(tr.word().gender())
+            .with_sense(tr.sense())
             .unwrap();
+            records.push(s.clone());
 
             Ok(s)
         }
 
-        async fn read_by_word(&self, _: &Word) -> Result<TranslationRecord, RepoReadError> {
+        /// `OWNER` and decks have the stub record, besides the created ones
+        async fn read_by_word(
+            &self,
+            owners: &[UserId],
+            word: &Word,
+        ) -> Result<Vec<TranslationRecord>, RepoReadError> {
+            if self.has_error.0.take() {
+                return Err(RepoReadError::Unknown);
+            }
+
+            owners
+                .iter()
+                .map(|o| {
+                    let stub = (self.has_record(o) && word.search_key() == text::search_key(WORD))
+                        .then(|| Self::record_of(o));
+                    let created = self.created_of(o).into_iter().filter(|tr| {
+                        tr.word().search_key() == word.search_key()
+                            && tr.word().value().1 == word.value().1
+                    });
+                    stub.into_iter().chain(created).collect::<Vec<_>>()
+                })
+                .find(|senses| !senses.is_empty())
+                .ok_or(RepoReadError::NotFound)
+        }
+
+        async fn read_by_ids(
+            &self,
+            owners: &[UserId],
+            ids: &[TranslationId],
+        ) -> Result<Vec<TranslationRecord>, RepoReadError> {
+            let records = self.list(owners).await?;
+
+            Ok(records
+                .into_iter()
+                .filter(|tr| tr.id().value().is_some() && ids.contains(tr.id()))
+                .collect())
+        }
+
+        async fn list(&self, owners: &[UserId]) -> Result<Vec<TranslationRecord>, RepoReadError> {
             if self.has_error.0.take() {
                 return Err(RepoReadError::Unknown);
             }
 
-            Ok(stub_translation_record(false))
+            Ok(owners
+                .iter()
+                .flat_map(|o| {
+                    let stub = self.has_record(o).then(|| Self::record_of(o));
+                    stub.into_iter().chain(self.created_of(o))
+                })
+                .collect())
+        }
+
+        async fn words(
+            &self,
+            owners: &[UserId],
+            lang: &Lang,
+        ) -> Result<Vec<(String, String)>, RepoReadError> {
+            let records = self.list(owners).await?;
+
+            Ok(records
+                .iter()
+                .map(|tr| tr.word().value())
+                .filter(|(_, l)| *l == lang)
+                .map(|(w, _)| (w.clone(), text::search_key(w)))
+                .collect())
+        }
+
+        async fn search(
+            &self,
+            owners: &[UserId],
+            key: &str,
+            lang: Option<&Lang>,
+            anywhere: bool,
+        ) -> Result<Vec<TranslationRecord>, RepoReadError> {
+            let records = self.list(owners).await?;
+            let hit = |candidate: &String| {
+                let candidate = text::search_key(candidate);
+                candidate.starts_with(key) || (anywhere && candidate.contains(key))
+            };
+
+            Ok(records
+                .into_iter()
+                .filter(|tr| {
+                    let (_, word, word_lang, translations, translation_lang) = tr.flat();
+                    let word_hit = lang.is_none_or(|l| l == word_lang)
+                        && text::search_key(word).starts_with(key);
+                    let translation_hit =
+                        lang.is_none_or(|l| l == translation_lang) && translations.iter().any(hit);
+                    word_hit || translation_hit
+                })
+                .collect())
         }
 
         async fn update(
@@ -75,14 +239,310 @@ pub mod repo_double {
                 return Err(RepoUpdateError::NotFound);
             }
 
+            let mut records = self.records.lock().unwrap();
+            if let Some(stored) = records
+                .iter_mut()
+                .find(|r| r.owner() == tr.owner() && r.id() == tr.id() && r.deleted_at().is_none())
+            {
+                *stored = tr.clone();
+            }
             Ok(tr.clone())
         }
 
-        async fn delete(&self, _id: &TranslationId) -> Result<(), RepoDeleteError> {
+        async fn delete(&self, owner: &UserId, id: &TranslationId) -> Result<(), RepoDeleteError> {
+            if self.has_error.0.take() {
+                return Err(RepoDeleteError::Unknown);
+            }
+            let mut records = self.records.lock().unwrap();
+            if let Some(stored) = records
+                .iter_mut()
+                .find(|tr| tr.owner() == owner && tr.id() == id && tr.deleted_at().is_none())
+            {
+                *stored = stored.clone().with_deleted_at(Some(SystemTime::now()));
+            }
+            Ok(())
+        }
+
+        async fn trash(&self, owners: &[UserId]) -> Result<Vec<TranslationRecord>, RepoReadError> {
+            if self.has_error.0.take() {
+                return Err(RepoReadError::Unknown);
+            }
+
+            let records = self.records.lock().unwrap();
+            Ok(records
+                .iter()
+                .filter(|tr| owners.contains(tr.owner()) && tr.deleted_at().is_some())
+                .cloned()
+                .collect())
+        }
+
+        async fn restore(
+            &self,
+            owner: &UserId,
+            id: &TranslationId,
+        ) -> Result<TranslationRecord, RepoUpdateError> {
+            if self.has_error.0.take() {
+                return Err(RepoUpdateError::Unknown);
+            }
+
+            let mut records = self.records.lock().unwrap();
+            let stored = records
+                .iter_mut()
+                .find(|tr| tr.owner() == owner && tr.id() == id && tr.deleted_at().is_some())
+                .ok_or(RepoUpdateError::NotFound)?;
+            *stored = stored.clone().with_deleted_at(None);
+            Ok(stored.clone())
+        }
+
+        async fn purge(
+            &self,
+            deleted_before: SystemTime,
+        ) -> Result<Vec<TranslationId>, RepoDeleteError> {
             if self.has_error.0.take() {
                 return Err(RepoDeleteError::Unknown);
             }
+
+            let mut records = self.records.lock().unwrap();
+            let expired =
+                |tr: &TranslationRecord| tr.deleted_at().is_some_and(|t| t < deleted_before);
+            let purged = records
+                .iter()
+                .filter(|tr| expired(tr))
+                .map(|tr| tr.id().clone())
+                .collect();
+            records.retain(|tr| !expired(tr));
+            Ok(purged)
+        }
+
+        async fn health(&self) -> Result<(), RepoHealthError> {
+            if self.has_error.0.take() {
+                return Err(RepoHealthError::Unavailable("provoked".to_string()));
+            }
             Ok(())
         }
     }
+
+    /// Keeps created users in memory
+    #[async_trait]
+    impl UserRepository for VociRepoDouble {
+        async fn create_user(
+            &self,
+            name: &str,
+            password_hash: &str,
+        ) -> Result<User, RepoCreateError> {
+            if self.has_error.0.take() {
+                return Err(RepoCreateError::Unknown);
+            }
+
+            let mut users = self.users.lock().unwrap();
+            if users.iter().any(|u| u.name() == name) {
+                return Err(RepoCreateError::Duplicate);
+            }
+            let user = User::new(&format!("user-{}", users.len()), name, password_hash);
+            users.push(user.clone());
+            Ok(user)
+        }
+
+        async fn read_user_by_name(&self, name: &str) -> Result<User, RepoReadError> {
+            if self.has_error.0.take() {
+                return Err(RepoReadError::Unknown);
+            }
+
+            let users = self.users.lock().unwrap();
+            users
+                .iter()
+                .find(|u| u.name() == name)
+                .cloned()
+                .ok_or(RepoReadError::NotFound)
+        }
+    }
+
+    /// Keeps created decks in memory
+    #[async_trait]
+    impl DeckRepository for VociRepoDouble {
+        async fn create_deck(&self, name: &str, owner: &UserId) -> Result<Deck, RepoCreateError> {
+            if self.has_error.0.take() {
+                return Err(RepoCreateError::Unknown);
+            }
+
+            let mut decks = self.decks.lock().unwrap();
+            let deck = Deck::new(
+                &format!("deck-{}", decks.len()),
+                name,
+                owner,
+                vec![],
+                vec![],
+            );
+            decks.push(deck.clone());
+            Ok(deck)
+        }
+
+        async fn read_deck(&self, id: &DeckId) -> Result<Deck, RepoReadError> {
+            if self.has_error.0.take() {
+                return Err(RepoReadError::Unknown);
+            }
+
+            let decks = self.decks.lock().unwrap();
+            decks
+                .iter()
+                .find(|d| d.id() == id)
+                .cloned()
+                .ok_or(RepoReadError::NotFound)
+        }
+
+        async fn add_editor(&self, id: &DeckId, editor: &UserId) -> Result<Deck, RepoUpdateError> {
+            self.update_deck(id, |d| {
+                let mut editors = d.editors().to_vec();
+                editors.push(editor.clone());
+                Deck::new(
+                    d.id().value(),
+                    d.name(),
+                    d.owner(),
+                    editors,
+                    d.subscribers().to_vec(),
+                )
+            })
+        }
+
+        async fn subscribe(&self, id: &DeckId, user: &UserId) -> Result<Deck, RepoUpdateError> {
+            self.update_deck(id, |d| {
+                let mut subscribers = d.subscribers().to_vec();
+                subscribers.push(user.clone());
+                Deck::new(
+                    d.id().value(),
+                    d.name(),
+                    d.owner(),
+                    d.editors().to_vec(),
+                    subscribers,
+                )
+            })
+        }
+
+        async fn unsubscribe(&self, id: &DeckId, user: &UserId) -> Result<Deck, RepoUpdateError> {
+            self.update_deck(id, |d| {
+                let mut subscribers = d.subscribers().to_vec();
+                subscribers.retain(|s| s != user);
+                Deck::new(
+                    d.id().value(),
+                    d.name(),
+                    d.owner(),
+                    d.editors().to_vec(),
+                    subscribers,
+                )
+            })
+        }
+
+        async fn subscriptions(&self, user: &UserId) -> Result<Vec<Deck>, RepoReadError> {
+            if self.has_error.0.take() {
+                return Err(RepoReadError::Unknown);
+            }
+
+            let decks = self.decks.lock().unwrap();
+            Ok(decks
+                .iter()
+                .filter(|d| d.is_subscribed(user))
+                .cloned()
+                .collect())
+        }
+    }
+
+    /// Keeps links in memory
+    #[async_trait]
+    impl LinkRepository for VociRepoDouble {
+        async fn add_link(&self, link: &Link) -> Result<(), RepoCreateError> {
+            if self.has_error.0.take() {
+                return Err(RepoCreateError::Unknown);
+            }
+
+            let mut links = self.links.lock().unwrap();
+            let [a, b] = link.ends();
+            let known = links
+                .iter()
+                .any(|l| l.relation() == link.relation() && l.other_end(a) == Some(b));
+            if !known {
+                links.push(link.clone());
+            }
+            Ok(())
+        }
+
+        async fn links_of(&self, ids: &[TranslationId]) -> Result<Vec<Link>, RepoReadError> {
+            if self.has_error.0.take() {
+                return Err(RepoReadError::Unknown);
+            }
+
+            let links = self.links.lock().unwrap();
+            Ok(links
+                .iter()
+                .filter(|l| l.ends().iter().any(|end| ids.contains(end)))
+                .cloned()
+                .collect())
+        }
+
+        async fn remove_links(&self, id: &TranslationId) -> Result<(), RepoDeleteError> {
+            if self.has_error.0.take() {
+                return Err(RepoDeleteError::Unknown);
+            }
+
+            self.links
+                .lock()
+                .unwrap()
+                .retain(|l| !l.ends().contains(id));
+            Ok(())
+        }
+    }
+
+    /// Keeps revisions in memory, in order of addition
+    #[async_trait]
+    impl RevisionRepository for VociRepoDouble {
+        async fn add_revision(&self, revision: &Revision) -> Result<Revision, RepoCreateError> {
+            if self.has_error.0.take() {
+                return Err(RepoCreateError::Unknown);
+            }
+
+            let mut revisions = self.revisions.lock().unwrap();
+            let stored = revision
+                .clone()
+                .with_id(&format!("rev-{}", revisions.len() + 1));
+            revisions.push(stored.clone());
+            Ok(stored)
+        }
+
+        async fn revisions_of(&self, id: &TranslationId) -> Result<Vec<Revision>, RepoReadError> {
+            if self.has_error.0.take() {
+                return Err(RepoReadError::Unknown);
+            }
+
+            let revisions = self.revisions.lock().unwrap();
+            Ok(revisions
+                .iter()
+                .filter(|r| r.record() == id)
+                .cloned()
+                .collect())
+        }
+    }
+
+    /// Keeps progress in memory
+    #[async_trait]
+    impl ProgressRepository for VociRepoDouble {
+        async fn record_answer(
+            &self,
+            user: &UserId,
+            id: &TranslationId,
+            correct: bool,
+        ) -> Result<Progress, RepoUpdateError> {
+            if self.has_error.0.take() {
+                return Err(RepoUpdateError::Unknown);
+            }
+
+            let key = (user.clone(), id.value().clone().unwrap_or_default());
+            let mut progress = self.progress.lock().unwrap();
+            let entry = progress.entry(key).or_default();
+            if correct {
+                entry.correct += 1;
+            } else {
+                entry.incorrect += 1;
+            }
+            Ok(entry.clone())
+        }
+    }
 }
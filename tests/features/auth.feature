@@ -0,0 +1,34 @@
+Feature: Protect Vocabulary Items with API Keys
+
+  Background:
+    Given a clean database is available
+    And the server is started with API keys
+
+  Scenario: Add a Translation without an API key
+    When I create a sound translation item
+    Then the http response is "UNAUTHORIZED"
+    And the error code is "missing_api_key"
+
+  Scenario: Add a Translation with an API key
+    Given I use the write API key
+    When I create a sound translation item
+    Then the http response is "OK"
+
+  Scenario: Read a Translation without an API key
+    Given I use the write API key
+    And there is a translation
+    When I use no API key
+    And I read an existing translation
+    Then the corresponding TranslationRecord is received
+    And the http response is "OK"
+
+  Scenario: Register a user with a write API key
+    Given I use the write API key
+    When I register user "erin"
+    Then the http response is "FORBIDDEN"
+    And the error code is "insufficient_scope"
+
+  Scenario: Register a user with the admin API key
+    Given I use the admin API key
+    When I register user "erin"
+    Then the http response is "OK"
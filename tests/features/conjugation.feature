@@ -0,0 +1,22 @@
+Feature: Conjugate French Verbs
+
+  Background:
+    Given a clean database is available
+    And the server is started
+
+  Scenario: Conjugating an irregular verb
+    When I create a translation for the word "aller"
+    And I conjugate "aller" regularly, with "vais" for "je" in the "present"
+    Then the http response is "OK"
+
+  Scenario: Practicing the form of an irregular verb
+    When I create a translation for the word "aller"
+    And I conjugate "aller" regularly, with "vais" for "je" in the "present"
+    And I give the "present" of "aller" for "je" as "je vais"
+    Then the form is graded "correct"
+
+  Scenario: Practicing a verb without conjugation
+    When I create a translation for the word "aller"
+    And I give the "present" of "aller" for "nous" as "allons"
+    Then the http response is "NOT_FOUND"
+    And the error code is "not_conjugated"
@@ -17,3 +17,24 @@ Feature: Create Vocabulary Items
     And I create a sound translation item
     Then the http response class is "Client Error"
     And the http response is "CONFLICT"
+    And the error code is "duplicate"
+
+  Scenario: Add a spelling variant of an existing Translation
+    When I create a sound translation item
+    And I create a translation for the word " Chien "
+    Then the http response is "CONFLICT"
+    And the error code is "duplicate"
+
+  Scenario: Add an existing Translation with its article
+    When I create a sound translation item
+    And I create a translation for the word "le chien"
+    Then the http response is "CONFLICT"
+
+  Scenario: Add a blank word
+    When I create a translation for the word "   "
+    Then the http response is "UNPROCESSABLE_ENTITY"
+    And the error code is "validation_failed"
+
+  Scenario: Server is ready
+    When I check the readiness
+    Then the http response is "OK"
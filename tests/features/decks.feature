@@ -0,0 +1,41 @@
+Feature: Share Decks with Subscribers
+
+  Background:
+    Given a clean database is available
+    And the server is started
+
+  Scenario: Subscribers read the words of a deck
+    Given "alice" publishes a deck
+    And there is a translation
+    When I log in as "bob"
+    And I subscribe to the deck
+    And I read an existing translation
+    Then the http response is "OK"
+
+  Scenario: Subscribers cannot edit the words of a deck
+    Given "alice" publishes a deck
+    And there is a translation
+    When I log in as "bob"
+    And I subscribe to the deck
+    And I update an existing translation
+    Then the http response is "FORBIDDEN"
+    And the error code is "read_only"
+
+  Scenario: Subscribers see the changes of editors
+    Given "alice" publishes a deck
+    And there is a translation
+    And "bob" subscribed to the deck
+    When I log in as "alice"
+    And I work on the deck
+    And I update an existing translation
+    And I log in as "bob"
+    And I read an existing translation
+    Then the updated TranslationRecord is received
+
+  Scenario: Decks are not readable without subscription
+    Given "alice" publishes a deck
+    When I log in as "bob"
+    And I work on the deck
+    And I read an existing translation
+    Then the http response is "FORBIDDEN"
+    And the error code is "not_subscribed"
@@ -14,10 +14,36 @@ Feature: Read, Update, Delete Vocabulary Items
     When I read a non-existing translation
     And the http response is "BAD_REQUEST"
 
+  Scenario: Reading a misspelled Translation
+    When I read the word "chein"
+    Then the http response is "NOT_FOUND"
+    And "chien" is suggested
+
+  Scenario: Reading all senses of a word
+    Given I create a translation for the word "avocat" in the sense "lawyer"
+    And I create a translation for the word "avocat" in the sense "fruit"
+    When I read the word "avocat"
+    Then the http response is "OK"
+    And 2 sense(s) received
+
+  Scenario: Creating a sense twice
+    Given I create a translation for the word "avocat" in the sense "fruit"
+    When I create a translation for the word "avocat" in the sense "Fruit"
+    Then the http response is "CONFLICT"
+
   Scenario: Deleting a Translation
     When I delete an existing translation
     Then the http response is "OK"
 
+  Scenario: Restoring a deleted Translation
+    When I delete an existing translation
+    And I open the trash
+    Then 1 record(s) in the trash
+    When I restore "chien" from the trash
+    Then the http response is "OK"
+    When I read the word "chien"
+    Then the http response is "OK"
+
   Scenario: Deleting a non existing Translation
     When I delete a non-existing translation
     Then the http response is "BAD_REQUEST"
@@ -27,6 +53,39 @@ Feature: Read, Update, Delete Vocabulary Items
     Then the updated TranslationRecord is received
     And the http response is "OK"
 
+  Scenario: Reverting an updated Translation
+    When I update an existing translation
+    And I read the history of "chien"
+    Then 2 revision(s) received
+    When I revert "chien" to its first revision
+    Then the http response is "OK"
+    When I read the history of "chien"
+    Then 3 revision(s) received
+
   Scenario: Update a non existing Translation
     When I update a non-existing translation
     Then the http response is "NOT_FOUND"
+    And the error code is "not_found"
+
+  Scenario: Drawing a quiz
+    When I draw a quiz
+    Then the http response is "OK"
+
+  Scenario: Answering a quiz question
+    When I answer the quiz for "chien" with "Fido"
+    Then the http response is "OK"
+    And the answer is graded "correct"
+
+  Scenario: Searching Translations ignoring case and accents
+    When I search for "CHI"
+    Then 1 translation(s) found
+
+  Scenario: Searching Translations without match
+    When I search for "katze"
+    Then 0 translation(s) found
+
+  Scenario: Reading a Translation with its word family
+    When I create a translation for the word "chienne"
+    And I link "chien" to "chienne" as "family"
+    And I read the word "chien" with its links
+    Then 1 linked record(s) received
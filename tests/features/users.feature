@@ -0,0 +1,25 @@
+Feature: Keep a Vocabulary per User
+
+  Background:
+    Given a clean database is available
+    And the server is started
+
+  Scenario: Two users add the same word
+    Given I am logged in as "alice"
+    And there is a translation
+    When I log in as "bob"
+    And I create a sound translation item
+    Then the http response is "OK"
+
+  Scenario: Words of other users are not visible
+    Given I am logged in as "alice"
+    And there is a translation
+    When I log in as "bob"
+    And I read an existing translation
+    Then the http response is "NOT_FOUND"
+
+  Scenario: Log in with a wrong password
+    Given user "alice" is registered
+    When I log in as "alice" with password "wrong password"
+    Then the http response is "UNAUTHORIZED"
+    And the error code is "invalid_credentials"
@@ -1,5 +1,6 @@
 use cucumber::{World, given, then, when};
 use reqwest::{Client, StatusCode};
+use serde::Serialize;
 use std::net::TcpListener;
 use std::path::{Path, PathBuf};
 use tokio::fs::File;
@@ -7,21 +8,30 @@ use tokio::io::AsyncReadExt;
 use tokio::sync::oneshot;
 
 /// Outer layer interna
+use vocabulaire::config::{AuthConfig, ServerConfig};
+use vocabulaire::domain::auth::{ApiKey, Scope, hash_api_key};
 use vocabulaire::domain::ports::TranslationRepository;
 use vocabulaire::driven::repository::mongo_repository;
 
 /// Testing utils
 use vocabulaire::test_utils::utils::shared;
 
-/// Interna used for testing convenience
-use vocabulaire::driving::rest_handler::vocis::{
-    CreateTranslationRequest, RequestTranslationByWord,
+/// Client of the REST API
+use vocabulaire::client::{
+    ClientError, ConjugationAnswerRequest, ConjugationRequest, CreateDeckRequest,
+    CreateTranslationRequest, CredentialsRequest, Form, LinkRequest, QuizAnswerRequest,
+    QuizAnswerResponse, RequestTranslationByWord, RevertRequest, SearchRequest,
+    TranslationResponse, VociClient,
 };
+use vocabulaire::domain::voci::Lang;
 
 const SERVER_URL: &str = "http://localhost";
-const API_ROUTE: &str = "voci/api/v1/translations";
+const LIVENESS_ROUTE: &str = "voci/health/live";
 const TEST_FILES: &str = "tests/features";
 const TEST_RESOURCES: &str = "tests/resources";
+const WRITE_KEY: &str = "wr1te-key";
+const ADMIN_KEY: &str = "adm1n-key";
+const PASSWORD: &str = "c0rrect horse";
 
 #[derive(Default, Debug, World)]
 pub struct DatabaseWorld {
@@ -31,8 +41,16 @@ pub struct DatabaseWorld {
     shutdown_tx: Option<oneshot::Sender<()>>,
     server_handle: Option<tokio::task::JoinHandle<()>>,
 
-    server_bytes: Option<actix_web::web::Bytes>,
+    auth: AuthConfig,
+    api_key: Option<String>,
+    token: Option<String>,
+    deck: Option<String>,
+    in_deck: bool,
+
+    served: Option<serde_json::Value>,
     server_status: StatusCode,
+    error_code: Option<String>,
+    suggestions: Vec<String>,
 }
 
 #[given("a clean database is available")]
@@ -47,13 +65,19 @@ async fn setup_database(world: &mut DatabaseWorld) {
 #[given("the server is started")]
 async fn start_server(world: &mut DatabaseWorld) {
     let repo = world.repo.as_ref().unwrap().clone();
+    let auth = world.auth.clone();
     let port = get_available_port();
     world.connection_port = Some(port);
 
     let (shutdown_tx, shutdown_rx) = oneshot::channel::<()>();
 
     let handle = tokio::spawn(async move {
-        match vocabulaire::server::create_server(repo, port).await {
+        let server_config = ServerConfig {
+            port,
+            auth,
+            ..ServerConfig::default()
+        };
+        match vocabulaire::server::create_server(repo, &server_config).await {
             Ok(server_future) => {
                 tokio::select! {
                     result = server_future => {
@@ -74,36 +98,162 @@ async fn start_server(world: &mut DatabaseWorld) {
     wait_for_server_on_port(port).await;
 }
 
+#[given("the server is started with API keys")]
+async fn start_protected_server(world: &mut DatabaseWorld) {
+    world.auth = AuthConfig {
+        keys: vec![
+            ApiKey {
+                name: "writer".to_string(),
+                hash: hash_api_key(WRITE_KEY),
+                scopes: vec![Scope::Write],
+            },
+            ApiKey {
+                name: "admin".to_string(),
+                hash: hash_api_key(ADMIN_KEY),
+                scopes: vec![Scope::Admin],
+            },
+        ],
+        protect_reads: false,
+        ..AuthConfig::default()
+    };
+    start_server(world).await;
+}
+
+#[given("I use the write API key")]
+async fn use_write_key(world: &mut DatabaseWorld) {
+    world.api_key = Some(WRITE_KEY.to_string());
+}
+
+#[given("I use the admin API key")]
+async fn use_admin_key(world: &mut DatabaseWorld) {
+    world.api_key = Some(ADMIN_KEY.to_string());
+}
+
+#[when("I use no API key")]
+async fn use_no_key(world: &mut DatabaseWorld) {
+    world.api_key = None;
+}
+
+#[given(expr = r"user {string} is registered")]
+async fn register(world: &mut DatabaseWorld, name: String) {
+    let request = CredentialsRequest {
+        name,
+        password: PASSWORD.to_string(),
+    };
+
+    match client(world).register(&request).await {
+        Ok(_) => {}
+        Err(e) if e.code() == Some("name_taken") => {}
+        Err(e) => panic!("Cannot register {}: {e}", request.name),
+    }
+}
+
+#[when(expr = r"I register user {string}")]
+async fn register_user(world: &mut DatabaseWorld, name: String) {
+    let request = CredentialsRequest {
+        name,
+        password: PASSWORD.to_string(),
+    };
+
+    let response = client(world).register(&request).await;
+    store_response(world, response);
+}
+
+#[given(expr = r"I am logged in as {string}")]
+#[when(expr = r"I log in as {string}")]
+async fn log_in(world: &mut DatabaseWorld, name: String) {
+    register(world, name.clone()).await;
+    log_in_with_password(world, name, PASSWORD.to_string()).await;
+}
+
+#[when(expr = r"I log in as {string} with password {string}")]
+async fn log_in_with_password(world: &mut DatabaseWorld, name: String, password: String) {
+    let request = CredentialsRequest { name, password };
+
+    let response = client(world).login(&request).await;
+
+    world.token = response.as_ref().ok().map(|t| t.access_token.clone());
+    world.in_deck = false;
+    store_response(world, response);
+}
+
+#[given(expr = r"{string} publishes a deck")]
+async fn publish_deck(world: &mut DatabaseWorld, name: String) {
+    log_in(world, name).await;
+    let request = CreateDeckRequest {
+        name: "Animals".to_string(),
+    };
+
+    let deck = client(world)
+        .create_deck(&request)
+        .await
+        .expect("Cannot create deck");
+
+    world.deck = Some(deck.id);
+    world.in_deck = true;
+}
+
+#[given(expr = r"{string} subscribed to the deck")]
+async fn subscribed(world: &mut DatabaseWorld, name: String) {
+    log_in(world, name).await;
+    subscribe(world).await;
+}
+
+#[when("I subscribe to the deck")]
+async fn subscribe(world: &mut DatabaseWorld) {
+    let deck = world.deck.clone().expect("no deck published");
+
+    let response = client(world).subscribe(&deck).await;
+
+    store_response(world, response);
+}
+
+#[when("I work on the deck")]
+async fn work_on_deck(world: &mut DatabaseWorld) {
+    world.in_deck = true;
+}
+
 #[when("I create a sound translation item")]
 #[given("there is a translation")]
 async fn add(world: &mut DatabaseWorld) {
-    let port = world.connection_port.unwrap_or(8082);
-    let url = format!("{SERVER_URL}:{port}/{API_ROUTE}");
-    let client = Client::new();
-
     let request = json_from_file(Path::new(TEST_RESOURCES).join("create_chien.json")).await;
     let tr_req: CreateTranslationRequest =
         serde_json::from_value(request.clone()).expect("unable to convert from json to request");
 
-    let response = client.post(url).json(&tr_req).send().await;
+    let response = client(world).create_translation(&tr_req).await;
 
-    match response {
-        Ok(r) => {
-            let status_code = r.status();
-            let bytes = r.bytes().await;
+    store_response(world, response);
+}
 
-            world.server_status = status_code;
-            world.server_bytes = bytes.ok();
-        }
-        Err(e) => println!("{:#?}", e),
-    }
+#[when(expr = r"I create a translation for the word {string}")]
+async fn add_word(world: &mut DatabaseWorld, word: String) {
+    let request = json_from_file(Path::new(TEST_RESOURCES).join("create_chien.json")).await;
+    let mut tr_req: CreateTranslationRequest =
+        serde_json::from_value(request).expect("unable to convert from json to request");
+    tr_req.word = word;
+
+    let response = client(world).create_translation(&tr_req).await;
+
+    store_response(world, response);
+}
+
+#[given(expr = r"I create a translation for the word {string} in the sense {string}")]
+#[when(expr = r"I create a translation for the word {string} in the sense {string}")]
+async fn add_sense(world: &mut DatabaseWorld, word: String, sense: String) {
+    let request = json_from_file(Path::new(TEST_RESOURCES).join("create_chien.json")).await;
+    let mut tr_req: CreateTranslationRequest =
+        serde_json::from_value(request).expect("unable to convert from json to request");
+    tr_req.word = word;
+    tr_req.sense = Some(sense);
+
+    let response = client(world).create_translation(&tr_req).await;
+
+    store_response(world, response);
 }
 
 #[when(expr = r"I {word} a(n) {word} translation")]
 async fn work_on_translation(world: &mut DatabaseWorld, operation: String, existence: String) {
-    let port = world.connection_port.unwrap_or(8082);
-    let url = format!("{SERVER_URL}:{port}/{API_ROUTE}");
-    let client = Client::new();
+    let client = client(world);
 
     let file_prefix = match existence.as_str() {
         "existing" => "chien",
@@ -111,14 +261,15 @@ async fn work_on_translation(world: &mut DatabaseWorld, operation: String, exist
         _ => panic!("Unsupported operation: {operation}"),
     };
 
-    let response = match operation.as_str() {
+    match operation.as_str() {
         "read" => {
             let request_file =
                 json_from_file(Path::new(TEST_RESOURCES).join(format!("{file_prefix}_word.json")))
                     .await;
             let request: RequestTranslationByWord =
                 serde_json::from_value(request_file.clone()).unwrap();
-            client.get(url).json(&request).send().await
+            let response = client.read_translation(&request).await;
+            store_response(world, response);
         }
         "update" => {
             let request = json_from_file(
@@ -127,7 +278,8 @@ async fn work_on_translation(world: &mut DatabaseWorld, operation: String, exist
             .await;
             let word_req: CreateTranslationRequest =
                 serde_json::from_value(request.clone()).unwrap();
-            client.put(url).json(&word_req).send().await
+            let response = client.update_translation(&word_req).await;
+            store_response(world, response);
         }
 
         "delete" => {
@@ -136,21 +288,260 @@ async fn work_on_translation(world: &mut DatabaseWorld, operation: String, exist
                     .await;
             let word_req: RequestTranslationByWord =
                 serde_json::from_value(request.clone()).unwrap();
-            client.delete(url).json(&word_req).send().await
+            let response = client.delete_translation(&word_req).await;
+            store_response(world, response);
         }
         _ => panic!("Unsupported operation: {operation}"),
     };
+}
 
-    match response {
-        Ok(r) => {
-            let status_code = r.status();
-            let bytes = r.bytes().await;
+#[when(expr = r"I read the word {string}")]
+async fn read_word(world: &mut DatabaseWorld, word: String) {
+    let request = RequestTranslationByWord {
+        word,
+        lang: Lang::fr,
+        sense: None,
+    };
 
-            world.server_status = status_code;
-            world.server_bytes = bytes.ok();
-        }
-        Err(e) => println!("{:#?}", e),
-    }
+    let response = client(world).read_translation(&request).await;
+    store_response(world, response);
+}
+
+#[when(expr = r"I read the word {string} with its links")]
+async fn read_word_with_links(world: &mut DatabaseWorld, word: String) {
+    let request = RequestTranslationByWord {
+        word,
+        lang: Lang::fr,
+        sense: None,
+    };
+
+    let response = client(world).read_translation_with_links(&request).await;
+    store_response(world, response);
+}
+
+#[when(expr = r"I link {string} to {string} as {string}")]
+async fn link_words(world: &mut DatabaseWorld, word: String, other: String, relation: String) {
+    let client = client(world);
+    let ids = [word_id(&client, word).await, word_id(&client, other).await];
+    let request = LinkRequest {
+        to: ids[1].clone(),
+        relation: from_name(relation),
+    };
+
+    let response = client.link_translations(&ids[0], &request).await;
+    store_response(world, response);
+}
+
+#[when(expr = r"I read the history of {string}")]
+async fn read_history(world: &mut DatabaseWorld, word: String) {
+    let client = client(world);
+    let id = word_id(&client, word).await;
+
+    let response = client.history(&id).await;
+    store_response(world, response);
+}
+
+#[when(expr = r"I revert {string} to its first revision")]
+async fn revert_to_first(world: &mut DatabaseWorld, word: String) {
+    let client = client(world);
+    let id = word_id(&client, word).await;
+    let history = client.history(&id).await.expect("Cannot read the history");
+    let request = RevertRequest {
+        revision: history[0].id.clone().expect("revision without id"),
+    };
+
+    let response = client.revert_translation(&id, &request).await;
+    store_response(world, response);
+}
+
+#[then(expr = r"{int} revision(s) received")]
+async fn revisions_received(world: &mut DatabaseWorld, count: usize) {
+    let revisions = world
+        .served
+        .as_ref()
+        .and_then(|v| v.as_array())
+        .expect("no history received");
+
+    assert_eq!(revisions.len(), count);
+}
+
+#[then(expr = r"{int} linked record(s) received")]
+async fn linked_received(world: &mut DatabaseWorld, count: usize) {
+    let links = world
+        .served
+        .as_ref()
+        .and_then(|v| v[0]["links"].as_array().cloned())
+        .unwrap_or_default();
+
+    assert_eq!(links.len(), count);
+}
+
+#[when("I open the trash")]
+async fn open_trash(world: &mut DatabaseWorld) {
+    let response = client(world).trash().await;
+    store_response(world, response);
+}
+
+#[when(expr = r"I restore {string} from the trash")]
+async fn restore_word(world: &mut DatabaseWorld, word: String) {
+    let client = client(world);
+    let trash = client.trash().await.expect("Cannot open the trash");
+    let id = trash
+        .into_iter()
+        .find(|tr| tr.word == word)
+        .and_then(|tr| tr.id)
+        .expect("word not in the trash");
+
+    let response = client.restore_translation(&id).await;
+    store_response(world, response);
+}
+
+#[then(expr = r"{int} record(s) in the trash")]
+async fn records_in_trash(world: &mut DatabaseWorld, count: usize) {
+    let trash = world
+        .served
+        .as_ref()
+        .and_then(|v| v.as_array())
+        .expect("no trash received");
+
+    assert_eq!(trash.len(), count);
+    assert!(trash.iter().all(|tr| tr["deleted_at"].is_u64()));
+}
+
+#[then(expr = r"{string} is suggested")]
+async fn suggested(world: &mut DatabaseWorld, word: String) {
+    assert!(
+        world.suggestions.contains(&word),
+        "{word} not in {:?}",
+        world.suggestions
+    );
+}
+
+#[when(expr = r"I search for {string}")]
+async fn search(world: &mut DatabaseWorld, q: String) {
+    let request = SearchRequest { q, lang: None };
+
+    let response = client(world).search_translations(&request).await;
+
+    store_response(world, response);
+}
+
+#[then(expr = r"{int} sense(s) received")]
+async fn senses_received(world: &mut DatabaseWorld, count: usize) {
+    let senses = world
+        .served
+        .as_ref()
+        .and_then(|v| v.as_array())
+        .expect("no senses received");
+
+    assert_eq!(senses.len(), count);
+}
+
+#[then(expr = r"{int} translation(s) found")]
+async fn translations_found(world: &mut DatabaseWorld, count: usize) {
+    let found = world
+        .served
+        .as_ref()
+        .and_then(|v| v.as_array())
+        .expect("no search result received");
+
+    assert_eq!(found.len(), count);
+}
+
+#[when("I check the readiness")]
+async fn check_readiness(world: &mut DatabaseWorld) {
+    let response = client(world).ready().await;
+
+    store_response(world, response);
+}
+
+#[when("I draw a quiz")]
+async fn draw_quiz(world: &mut DatabaseWorld) {
+    let response = client(world).draw_quiz(5).await;
+
+    store_response(world, response);
+}
+
+#[when(expr = r"I answer the quiz for {string} with {string}")]
+async fn answer_quiz(world: &mut DatabaseWorld, word: String, answer: String) {
+    let request = QuizAnswerRequest {
+        word,
+        lang: Lang::fr,
+        answer,
+        require_article: false,
+    };
+
+    let response = client(world).answer_quiz(&request).await;
+
+    store_response(world, response);
+}
+
+#[when(expr = r"I conjugate {string} regularly, with {string} for {string} in the {string}")]
+async fn conjugate(
+    world: &mut DatabaseWorld,
+    word: String,
+    form: String,
+    person: String,
+    tense: String,
+) {
+    let request = ConjugationRequest {
+        word,
+        sense: None,
+        regular: true,
+        tenses: vec![],
+        overrides: vec![Form {
+            tense: from_name(tense),
+            person: from_name(person),
+            form,
+        }],
+    };
+
+    let response = client(world).conjugate_translation(&request).await;
+
+    store_response(world, response);
+}
+
+#[when(expr = r"I give the {string} of {string} for {string} as {string}")]
+async fn answer_conjugation(
+    world: &mut DatabaseWorld,
+    tense: String,
+    word: String,
+    person: String,
+    answer: String,
+) {
+    let request = ConjugationAnswerRequest {
+        word,
+        sense: None,
+        tense: from_name(tense),
+        person: from_name(person),
+        answer,
+    };
+
+    let response = client(world).answer_conjugation(&request).await;
+
+    store_response(world, response);
+}
+
+#[then(expr = r"the form is graded {string}")]
+async fn form_graded(world: &mut DatabaseWorld, grade: String) {
+    let correct = world
+        .served
+        .as_ref()
+        .and_then(|v| v["correct"].as_bool())
+        .expect("no conjugation answer received");
+
+    assert_eq!(correct, grade == "correct");
+}
+
+#[then(expr = r"the answer is graded {string}")]
+async fn answer_graded(world: &mut DatabaseWorld, grade: String) {
+    let served_response: QuizAnswerResponse = world
+        .served
+        .clone()
+        .and_then(|v| serde_json::from_value(v).ok())
+        .expect("no quiz answer received");
+
+    assert_eq!(served_response.correct, grade == "correct");
 }
 
 #[then(expr = r"the http response is {string}")]
@@ -160,12 +551,20 @@ async fn http_response(world: &mut DatabaseWorld, status_code: String) {
         "CONFLICT" => StatusCode::CONFLICT,
         "BAD_REQUEST" => StatusCode::BAD_REQUEST,
         "NOT_FOUND" => StatusCode::NOT_FOUND,
+        "UNAUTHORIZED" => StatusCode::UNAUTHORIZED,
+        "FORBIDDEN" => StatusCode::FORBIDDEN,
+        "UNPROCESSABLE_ENTITY" => StatusCode::UNPROCESSABLE_ENTITY,
         _ => StatusCode::NOT_IMPLEMENTED,
     };
 
     assert_eq!(world.server_status, code);
 }
 
+#[then(expr = r"the error code is {string}")]
+async fn error_code(world: &mut DatabaseWorld, code: String) {
+    assert_eq!(world.error_code, Some(code));
+}
+
 #[then(expr = r"the http response class is {string}")]
 async fn http_response_class(world: &mut DatabaseWorld, status_class: String) {
     let status_fn: fn(&StatusCode) -> bool = match status_class.as_ref() {
@@ -178,10 +577,7 @@ async fn http_response_class(world: &mut DatabaseWorld, status_class: String) {
 
 #[then("the corresponding TranslationRecord is received")]
 async fn got_read_translation(world: &mut DatabaseWorld) {
-    let served_response: Option<serde_json::Value> = world
-        .server_bytes
-        .as_ref()
-        .and_then(|b| serde_json::from_slice(b).ok());
+    let served_response: Option<serde_json::Value> = world.served.clone();
 
     let expected_translationrecord =
         json_from_file(Path::new(TEST_RESOURCES).join("create_chien.json")).await;
@@ -189,7 +585,7 @@ async fn got_read_translation(world: &mut DatabaseWorld) {
     let keys_equal = ["word", "lang", "translations", "translation_lang"];
     let fields_equal = compare_fields_by_key(
         &expected_translationrecord,
-        served_response.as_ref().unwrap(),
+        &served_response.as_ref().unwrap()[0],
         &keys_equal,
     );
 
@@ -198,10 +594,7 @@ async fn got_read_translation(world: &mut DatabaseWorld) {
 
 #[then("the updated TranslationRecord is received")]
 async fn updated_translation(world: &mut DatabaseWorld) {
-    let served_response: Option<serde_json::Value> = world
-        .server_bytes
-        .as_ref()
-        .and_then(|b| serde_json::from_slice(b).ok());
+    let served_response: Option<serde_json::Value> = world.served.clone();
 
     let expected_translationrecord =
         json_from_file(Path::new(TEST_RESOURCES).join("chien_update.json")).await;
@@ -248,6 +641,98 @@ async fn main() {
         })
         .run(format!("{TEST_FILES}/rud.feature"))
         .await;
+
+    DatabaseWorld::cucumber()
+        .max_concurrent_scenarios(1)
+        .after(|_feature, _rule, _scenario, _ev, world| {
+            Box::pin(async move {
+                if let Some(world) = world {
+                    shutdown_server(world).await;
+                }
+            })
+        })
+        .run(format!("{TEST_FILES}/auth.feature"))
+        .await;
+
+    DatabaseWorld::cucumber()
+        .max_concurrent_scenarios(1)
+        .after(|_feature, _rule, _scenario, _ev, world| {
+            Box::pin(async move {
+                if let Some(world) = world {
+                    shutdown_server(world).await;
+                }
+            })
+        })
+        .run(format!("{TEST_FILES}/users.feature"))
+        .await;
+
+    DatabaseWorld::cucumber()
+        .max_concurrent_scenarios(1)
+        .after(|_feature, _rule, _scenario, _ev, world| {
+            Box::pin(async move {
+                if let Some(world) = world {
+                    shutdown_server(world).await;
+                }
+            })
+        })
+        .run(format!("{TEST_FILES}/decks.feature"))
+        .await;
+}
+
+fn client(world: &DatabaseWorld) -> VociClient {
+    let port = world.connection_port.unwrap_or(8082);
+    let mut client = VociClient::new(&format!("{SERVER_URL}:{port}"));
+    if let Some(key) = &world.api_key {
+        client = client.with_api_key(key);
+    }
+    if let Some(token) = &world.token {
+        client = client.with_token(token);
+    }
+    if let (Some(deck), true) = (&world.deck, world.in_deck) {
+        client = client.with_deck(deck);
+    }
+    client
+}
+
+/// Keeps the status and the served body of a client call for later assertions
+fn store_response<T: Serialize>(world: &mut DatabaseWorld, response: Result<T, ClientError>) {
+    match response {
+        Ok(body) => {
+            world.server_status = StatusCode::OK;
+            world.served = serde_json::to_value(body).ok();
+            world.error_code = None;
+            world.suggestions = Vec::new();
+        }
+        Err(e) => {
+            world.server_status = e.status().unwrap_or(StatusCode::NOT_IMPLEMENTED);
+            world.served = None;
+            world.error_code = e.code().map(str::to_string);
+            world.suggestions = e
+                .problem()
+                .and_then(|p| p.suggestions.clone())
+                .unwrap_or_default();
+            println!("{:#?}", e);
+        }
+    }
+}
+
+/// Relation, tense or person by its name on the wire, e.g. "family" or "nous"
+fn from_name<T: serde::de::DeserializeOwned>(name: String) -> T {
+    serde_json::from_value(serde_json::Value::String(name)).expect("unknown name")
+}
+
+/// ID of the first sense of a French word
+async fn word_id(client: &VociClient, word: String) -> String {
+    let request = RequestTranslationByWord {
+        word,
+        lang: Lang::fr,
+        sense: None,
+    };
+    let senses: Vec<TranslationResponse> = client
+        .read_translation(&request)
+        .await
+        .expect("Cannot read word");
+    senses[0].id.clone().expect("record without id")
 }
 
 async fn json_from_file(file_path: PathBuf) -> serde_json::Value {
@@ -271,7 +756,7 @@ fn get_available_port() -> u16 {
 
 async fn wait_for_server_on_port(port: u16) {
     let client = Client::new();
-    let url = format!("{SERVER_URL}:{port}/{API_ROUTE}");
+    let url = format!("{SERVER_URL}:{port}/{LIVENESS_ROUTE}");
 
     for attempt in 1..=30 {
         match client.get(&url).send().await {
@@ -292,10 +777,10 @@ async fn shutdown_server(world: &mut DatabaseWorld) {
         let _ = shutdown_tx.send(());
     }
 
-    if let Some(handle) = world.server_handle.take() {
-        if let Err(e) = tokio::time::timeout(tokio::time::Duration::from_secs(5), handle).await {
-            println!("Server shutdown error {e}");
-        }
+    if let Some(handle) = world.server_handle.take()
+        && let Err(e) = tokio::time::timeout(tokio::time::Duration::from_secs(5), handle).await
+    {
+        println!("Server shutdown error {e}");
     }
 
     // Give the port time to be released